@@ -0,0 +1,551 @@
+//! # Mush fake AoC
+//!
+//! Faux serveur Advent of Code pour tester `mush` de bout en bout, sans jamais
+//! contacter adventofcode.com.
+//!
+//! Le serveur sert des fixtures rangées comme suit :
+//!
+//! ```text
+//! fixtures/
+//! └── 2024/
+//!     ├── calendar.html            # GET  /2024
+//!     ├── leaderboard/123456.json  # GET  /2024/leaderboard/private/view/123456.json
+//!     └── day01/
+//!         ├── puzzle.html          # GET  /2024/day/1
+//!         ├── input.txt            # GET  /2024/day/1/input
+//!         └── answers.txt          # POST /2024/day/1/answer (réponse de la partie N à la ligne N)
+//! ```
+//!
+//! Les comportements du vrai site se configurent sur [`FakeAoc`] : heures d'ouverture
+//! des puzzles, limite de requêtes, délai après une mauvaise réponse et session expirée.
+//!
+//! ```
+//! let server = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+//!     .session("cookie")
+//!     .start()
+//!     .unwrap();
+//! let url = format!("{}/2024/day/1/input", server.url());
+//! assert!(url.starts_with("http://127.0.0.1:"));
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Fixtures fournies avec la crate (jour 1 de 2024, calendrier et classement privé 123456)
+pub const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+/// Cookie de session accepté par défaut
+pub const DEFAULT_SESSION: &str = "fake-session";
+
+/// Message du site pour un input demandé sans session valide
+pub const LOGIN_MESSAGE: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+/// Message du site pour un puzzle pas encore ouvert
+pub const LOCKED_MESSAGE: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.";
+/// Réponse correcte
+pub const RIGHT_ANSWER: &str = "That's the right answer!";
+/// Mauvaise réponse
+pub const WRONG_ANSWER: &str = "That's not the right answer.";
+/// Réponse envoyée pendant le délai qui suit une mauvaise réponse
+pub const TOO_RECENT: &str = "You gave an answer too recently; you have to wait after submitting an answer before trying again.";
+/// Réponse à une partie déjà résolue ou pas encore accessible
+pub const WRONG_LEVEL: &str =
+    "You don't seem to be solving the right level.  Did you already complete it?";
+
+/// Configuration du faux serveur, à démarrer avec [`FakeAoc::start`]
+#[derive(Debug, Clone)]
+pub struct FakeAoc {
+    fixtures: PathBuf,
+    session: String,
+    session_expired: bool,
+    unlocks: HashMap<(u16, u8), SystemTime>,
+    rate_limit: Option<(usize, Duration)>,
+    wrong_answer_cooldown: Duration,
+}
+
+impl FakeAoc {
+    /// Serveur servant les fixtures d'un répertoire, sans restriction.
+    ///
+    /// La session acceptée est [`DEFAULT_SESSION`], tous les puzzles sont ouverts et
+    /// le délai après une mauvaise réponse est de 60 secondes, comme sur le site.
+    pub fn new(fixtures: impl Into<PathBuf>) -> FakeAoc {
+        FakeAoc {
+            fixtures: fixtures.into(),
+            session: DEFAULT_SESSION.to_string(),
+            session_expired: false,
+            unlocks: HashMap::new(),
+            rate_limit: None,
+            wrong_answer_cooldown: Duration::from_secs(60),
+        }
+    }
+
+    /// Cookie de session accepté
+    pub fn session(mut self, session: impl Into<String>) -> FakeAoc {
+        self.session = session.into();
+        self
+    }
+
+    /// Refuse toutes les sessions, comme après l'expiration du cookie
+    pub fn session_expired(mut self, expired: bool) -> FakeAoc {
+        self.session_expired = expired;
+        self
+    }
+
+    /// Heure d'ouverture d'un puzzle : avant, sa page, son input et ses réponses sont en 404
+    pub fn unlock_at(mut self, year: u16, day: u8, time: SystemTime) -> FakeAoc {
+        self.unlocks.insert((year, day), time);
+        self
+    }
+
+    /// Répond 429 au-delà de `requests` requêtes sur une fenêtre glissante de `per`
+    pub fn rate_limit(mut self, requests: usize, per: Duration) -> FakeAoc {
+        self.rate_limit = Some((requests, per));
+        self
+    }
+
+    /// Délai pendant lequel les réponses sont refusées après une mauvaise réponse
+    pub fn wrong_answer_cooldown(mut self, cooldown: Duration) -> FakeAoc {
+        self.wrong_answer_cooldown = cooldown;
+        self
+    }
+
+    /// Démarre le serveur sur un port libre de `127.0.0.1`.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si aucun port n'est disponible.
+    pub fn start(self) -> Result<Server> {
+        self.listen("127.0.0.1:0")
+    }
+
+    /// Démarre le serveur sur une adresse donnée (`127.0.0.1:8080`...).
+    ///
+    /// Les requêtes sont traitées dans un thread, jusqu'à ce que le [`Server`] soit détruit.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'adresse ne peut pas être écoutée.
+    pub fn listen(self, addr: &str) -> Result<Server> {
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| anyhow!("Impossible d'écouter sur {} : {}", addr, e))?;
+        let local = http
+            .server_addr()
+            .to_ip()
+            .context("Le serveur n'écoute pas sur une adresse IP")?;
+        let http = Arc::new(http);
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let thread = {
+            let http = Arc::clone(&http);
+            let state = Arc::clone(&state);
+            std::thread::spawn(move || {
+                for mut request in http.incoming_requests() {
+                    let mut body = String::new();
+                    let _ = request.as_reader().read_to_string(&mut body);
+                    let recorded = RecordedRequest {
+                        method: request.method().to_string(),
+                        path: request.url().to_string(),
+                        headers: request
+                            .headers()
+                            .iter()
+                            .map(|h| (h.field.to_string(), h.value.to_string()))
+                            .collect(),
+                        body,
+                    };
+                    let reply = {
+                        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                        let reply = self.handle(&mut state, &recorded);
+                        state.requests.push(recorded);
+                        reply
+                    };
+                    let _ = request.respond(reply.into_response());
+                }
+            })
+        };
+
+        Ok(Server {
+            url: format!("http://{}", local),
+            http,
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    /// Répond à une requête comme le ferait adventofcode.com
+    fn handle(&self, state: &mut State, request: &RecordedRequest) -> Reply {
+        if let Some((max, per)) = self.rate_limit {
+            let now = Instant::now();
+            while state
+                .recent
+                .front()
+                .is_some_and(|time| now.duration_since(*time) >= per)
+            {
+                state.recent.pop_front();
+            }
+            if state.recent.len() >= max {
+                return Reply::new(429, "Too Many Requests");
+            }
+            state.recent.push_back(now);
+        }
+
+        let path = request.path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let logged_in = self.is_logged_in(request);
+
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", [year]) => match year.parse::<u16>() {
+                Ok(year) => self.fixture(&format!("{}/calendar.html", year)),
+                Err(_) => Reply::not_found(),
+            },
+            ("GET", [year, "day", day]) => self.with_day(year, day, |year, day| {
+                self.fixture(&format!("{}/day{:02}/puzzle.html", year, day))
+            }),
+            ("GET", [year, "day", day, "input"]) => self.with_day(year, day, |year, day| {
+                if !logged_in {
+                    return Reply::new(400, LOGIN_MESSAGE);
+                }
+                self.fixture(&format!("{}/day{:02}/input.txt", year, day))
+            }),
+            ("POST", [year, "day", day, "answer"]) => self.with_day(year, day, |year, day| {
+                if !logged_in {
+                    return Reply::redirect(&format!("/{}/day/{}", year, day));
+                }
+                self.submit(state, year, day, &request.body)
+            }),
+            ("GET", [year, "leaderboard", "private", "view", file]) => {
+                if !logged_in {
+                    return Reply::redirect(&format!("/{}/leaderboard", year));
+                }
+                match file.strip_suffix(".json") {
+                    Some(id) if id.chars().all(|c| c.is_ascii_digit()) => {
+                        let mut reply = self.fixture(&format!("{}/leaderboard/{}.json", year, id));
+                        reply.content_type = "application/json";
+                        reply
+                    }
+                    _ => Reply::not_found(),
+                }
+            }
+            _ => Reply::not_found(),
+        }
+    }
+
+    /// Vérifie le cookie `session` de la requête
+    fn is_logged_in(&self, request: &RecordedRequest) -> bool {
+        !self.session_expired
+            && request.header("cookie").is_some_and(|cookie| {
+                cookie
+                    .split(';')
+                    .filter_map(|pair| pair.trim().split_once('='))
+                    .any(|(name, value)| name == "session" && value == self.session)
+            })
+    }
+
+    /// Lit l'année et le jour d'une URL et vérifie que le puzzle est ouvert
+    fn with_day(&self, year: &str, day: &str, reply: impl FnOnce(u16, u8) -> Reply) -> Reply {
+        let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+            return Reply::not_found();
+        };
+        if self
+            .unlocks
+            .get(&(year, day))
+            .is_some_and(|unlock| SystemTime::now() < *unlock)
+        {
+            return Reply::new(404, LOCKED_MESSAGE);
+        }
+        reply(year, day)
+    }
+
+    /// Vérifie une réponse (`level=1&answer=42`) avec `answers.txt`
+    fn submit(&self, state: &mut State, year: u16, day: u8, body: &str) -> Reply {
+        let form: HashMap<String, String> = body
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (decode_form(key), decode_form(value)))
+            .collect();
+        let (Some(level), Some(answer)) = (
+            form.get("level").and_then(|level| level.parse::<u8>().ok()),
+            form.get("answer"),
+        ) else {
+            return Reply::new(400, "Bad Request");
+        };
+
+        let Ok(answers) = fs::read_to_string(
+            self.fixtures
+                .join(format!("{}/day{:02}/answers.txt", year, day)),
+        ) else {
+            return Reply::not_found();
+        };
+
+        let now = Instant::now();
+        if let Some(until) = state.cooldown_until.filter(|until| now < *until) {
+            let left = until.duration_since(now).as_secs().max(1);
+            return Reply::article(&format!("{} You have {}s left to wait.", TOO_RECENT, left));
+        }
+
+        let solved = state.solved.entry((year, day)).or_default();
+        if level != *solved + 1 || level > 2 {
+            return Reply::article(WRONG_LEVEL);
+        }
+        let expected = answers.lines().nth(usize::from(level) - 1).map(str::trim);
+        if expected == Some(answer.trim()) {
+            *solved = level;
+            Reply::article(RIGHT_ANSWER)
+        } else {
+            state.cooldown_until = Some(now + self.wrong_answer_cooldown);
+            Reply::article(&format!(
+                "{} If you're stuck, make sure you're using the full input data.",
+                WRONG_ANSWER
+            ))
+        }
+    }
+
+    /// Contenu d'une fixture, 404 si elle n'existe pas
+    fn fixture(&self, relative: &str) -> Reply {
+        match fs::read_to_string(self.fixtures.join(relative)) {
+            Ok(content) => Reply {
+                status: 200,
+                body: content,
+                content_type: if relative.ends_with(".html") {
+                    "text/html; charset=utf-8"
+                } else {
+                    "text/plain; charset=utf-8"
+                },
+                location: None,
+            },
+            Err(_) => Reply::not_found(),
+        }
+    }
+}
+
+/// Serveur démarré, arrêté lorsqu'il est détruit
+pub struct Server {
+    url: String,
+    http: Arc<tiny_http::Server>,
+    state: Arc<Mutex<State>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    /// URL de base du serveur (`http://127.0.0.1:PORT`), à utiliser à la place de
+    /// `https://adventofcode.com`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Requêtes reçues jusqu'ici, dans l'ordre
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .requests
+            .clone()
+    }
+
+    /// Nombre de parties résolues d'un jour (0, 1 ou 2)
+    pub fn solved(&self, year: u16, day: u8) -> u8 {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .solved
+            .get(&(year, day))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Met fin au délai qui suit une mauvaise réponse, pour tester la réponse suivante sans attendre
+    pub fn clear_cooldown(&self) {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .cooldown_until = None;
+    }
+
+    /// Bloque jusqu'à l'arrêt du serveur (pour le binaire `mush-fakeaoc`)
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.http.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Requête reçue par le serveur
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Valeur d'un en-tête, sans tenir compte de la casse de son nom
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// État partagé entre les requêtes
+#[derive(Default)]
+struct State {
+    requests: Vec<RecordedRequest>,
+    /// Heures des requêtes de la fenêtre de la limite de requêtes
+    recent: VecDeque<Instant>,
+    /// Fin du délai qui suit une mauvaise réponse
+    cooldown_until: Option<Instant>,
+    /// Parties résolues par jour
+    solved: HashMap<(u16, u8), u8>,
+}
+
+/// Réponse HTTP à envoyer
+struct Reply {
+    status: u16,
+    body: String,
+    content_type: &'static str,
+    location: Option<String>,
+}
+
+impl Reply {
+    fn new(status: u16, body: &str) -> Reply {
+        Reply {
+            status,
+            body: body.to_string(),
+            content_type: "text/plain; charset=utf-8",
+            location: None,
+        }
+    }
+
+    fn not_found() -> Reply {
+        Reply::new(404, "404 Not Found")
+    }
+
+    fn redirect(location: &str) -> Reply {
+        Reply {
+            location: Some(location.to_string()),
+            ..Reply::new(302, "")
+        }
+    }
+
+    /// Page de résultat d'une réponse, comme celle du site
+    fn article(message: &str) -> Reply {
+        Reply {
+            body: format!("<main>\n<article><p>{}</p></article>\n</main>\n", message),
+            content_type: "text/html; charset=utf-8",
+            ..Reply::new(200, "")
+        }
+    }
+
+    fn into_response(self) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+        let mut response = tiny_http::Response::from_string(self.body)
+            .with_status_code(self.status)
+            .with_header(header("Content-Type", self.content_type));
+        if let Some(location) = self.location {
+            response = response.with_header(header("Location", &location));
+        }
+        response
+    }
+}
+
+fn header(field: &str, value: &str) -> tiny_http::Header {
+    tiny_http::Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("en-tête ASCII")
+}
+
+/// Décode une valeur de formulaire (`+` et `%XX`)
+fn decode_form(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_and_limits() {
+        let server = FakeAoc::new(FIXTURES).start().unwrap();
+        let client = reqwest::blocking::Client::new();
+        let cookie = format!("session={}", DEFAULT_SESSION);
+        let submit = |level: u8, answer: &str| {
+            client
+                .post(format!("{}/2024/day/1/answer", server.url()))
+                .header("Cookie", &cookie)
+                .body(format!("level={}&answer={}", level, answer))
+                .send()
+                .unwrap()
+                .text()
+                .unwrap()
+        };
+
+        assert!(submit(2, "1337").contains(WRONG_LEVEL));
+        assert!(submit(1, "41").contains(WRONG_ANSWER));
+        assert!(submit(1, "42").contains(TOO_RECENT));
+        server.clear_cooldown();
+        assert!(submit(1, "42").contains(RIGHT_ANSWER));
+        assert!(submit(2, "1337").contains(RIGHT_ANSWER));
+        assert_eq!(server.solved(2024, 1), 2);
+
+        let leaderboard = client
+            .get(format!(
+                "{}/2024/leaderboard/private/view/123456.json",
+                server.url()
+            ))
+            .header("Cookie", &cookie)
+            .send()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&leaderboard.text().unwrap()).unwrap();
+        assert_eq!(json["members"]["123456"]["stars"], 2);
+        let calendar = client.get(format!("{}/2024", server.url())).send().unwrap();
+        assert!(calendar.text().unwrap().contains("calendar-day1"));
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let server = FakeAoc::new(FIXTURES)
+            .rate_limit(2, Duration::from_secs(60))
+            .start()
+            .unwrap();
+        let client = reqwest::blocking::Client::new();
+        let statuses: Vec<u16> = (0..3)
+            .map(|_| {
+                let url = format!("{}/2024/day/1", server.url());
+                client.get(url).send().unwrap().status().as_u16()
+            })
+            .collect();
+        assert_eq!(statuses, [200, 200, 429]);
+    }
+}
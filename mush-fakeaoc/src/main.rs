@@ -0,0 +1,26 @@
+//! Lance le faux serveur Advent of Code sur les fixtures d'un répertoire.
+//!
+//! ```bash
+//! cargo run -p mush-fakeaoc -- [FIXTURES] [ADRESSE]
+//! ```
+
+use anyhow::Result;
+use mush_fakeaoc::{FakeAoc, DEFAULT_SESSION, FIXTURES};
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let fixtures = args.next().unwrap_or_else(|| FIXTURES.to_string());
+    let addr = args.next().unwrap_or_else(|| "127.0.0.1:8025".to_string());
+
+    let session = std::env::var("AOC_SESSION").unwrap_or_else(|_| DEFAULT_SESSION.to_string());
+    let server = FakeAoc::new(&fixtures).session(&session).listen(&addr)?;
+
+    println!(
+        "🎄 Faux Advent of Code sur {} (fixtures : {})",
+        server.url(),
+        fixtures
+    );
+    println!("   Session acceptée : {}", session);
+    server.wait();
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! # Mush helpers
+//!
+//! Fonctions utilitaires partagées par `mush` et par les solutions Advent of Code.
+
+pub mod ocr;
@@ -0,0 +1,153 @@
+//! Reconnaissance des lettres dessinées en `#`/`.` par certains puzzles
+//! (2016 jour 8, 2018 jour 10, 2019 jours 8 et 11, 2021 jour 13, 2022 jour 10...).
+//!
+//! Deux polices sont reconnues : celle de 4x6 (la plus courante) et celle de 6x10
+//! (2018 jour 10). Les lettres sont séparées par les colonnes vides.
+
+/// Police de 6 lignes de haut
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Police de 10 lignes de haut
+#[rustfmt::skip]
+const FONT_10: &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+/// Lit les lettres d'une grille dessinée.
+///
+/// Les pixels allumés sont `#` ou `█`, les pixels éteints `.` ou des espaces. Les lignes
+/// vides au-dessus et en dessous du dessin sont ignorées.
+///
+/// Retourne `None` si la grille ne fait pas 6 ou 10 lignes de haut, contient d'autres
+/// caractères, ou si une des lettres est inconnue.
+///
+/// # Exemple
+///
+/// ```
+/// let grid = "#..#.###.\n#..#.#..#\n####.###.\n#..#.#..#\n#..#.#..#\n#..#.###.";
+/// assert_eq!(mush_helpers::ocr::parse(grid).as_deref(), Some("HB"));
+/// ```
+pub fn parse(grid: &str) -> Option<String> {
+    let mut rows: Vec<Vec<bool>> = Vec::new();
+    for line in grid.lines() {
+        let row = line
+            .trim_end()
+            .chars()
+            .map(|c| match c {
+                '#' | '█' => Some(true),
+                '.' | ' ' => Some(false),
+                _ => None,
+            })
+            .collect::<Option<Vec<bool>>>()?;
+        rows.push(row);
+    }
+    while rows.last().is_some_and(|row| !row.contains(&true)) {
+        rows.pop();
+    }
+    let blank = rows.iter().take_while(|row| !row.contains(&true)).count();
+    rows.drain(..blank);
+
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if !lit(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && lit(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|x| if row.get(x) == Some(&true) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+        let (letter, _) = font.iter().find(|(_, pattern)| *pattern == glyph)?;
+        text.push(*letter);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AOC: &str = ".##...##...##.\n#..#.#..#.#..#\n#..#.#..#.#...\n####.#..#.#...\n#..#.#..#.#..#\n#..#..##...##.";
+
+    #[test]
+    fn test_parse_small_font() {
+        assert_eq!(parse(AOC).as_deref(), Some("AOC"));
+        // Pixels éteints en espaces et pixels allumés en blocs
+        let blocks = AOC.replace('.', " ").replace('#', "█");
+        assert_eq!(parse(&blocks).as_deref(), Some("AOC"));
+    }
+
+    #[test]
+    fn test_parse_tall_font() {
+        // Police 6x10 (2018 jour 10)
+        let tall = "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#";
+        assert_eq!(parse(tall).as_deref(), Some("X"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_glyphs() {
+        // Lettre inconnue ou hauteur inattendue
+        assert_eq!(parse("###\n#.#\n###\n#.#\n###\n#.#"), None);
+        assert_eq!(parse("#..#\n####"), None);
+    }
+
+    #[test]
+    fn test_every_glyph_is_recognized() {
+        for (letter, glyph) in FONT_6.iter().chain(FONT_10) {
+            assert_eq!(parse(glyph), Some(letter.to_string()), "{}", letter);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Résultat de la compilation d'un ensemble de packages
+#[derive(Debug, Default)]
+pub struct BuildOutput {
+    /// Durée totale de la compilation
+    pub duration: Duration,
+    /// Exécutable produit pour chaque package compilé, par nom de binaire
+    pub executables: HashMap<String, PathBuf>,
+    /// Exécutable de tests de chaque package compilé avec `cargo build --tests`
+    pub tests: HashMap<String, PathBuf>,
+    /// Erreurs de compilation (diagnostics rendus) de chaque package en échec
+    pub errors: HashMap<String, String>,
+}
+
+impl BuildOutput {
+    /// Lit les exécutables et les erreurs dans la sortie JSON de `cargo build`
+    pub fn from_messages(stdout: &str) -> BuildOutput {
+        let mut build = BuildOutput::default();
+        for line in stdout.lines() {
+            match serde_json::from_str(line) {
+                Ok(Event::CompilerArtifact {
+                    target,
+                    profile,
+                    executable: Some(executable),
+                }) => {
+                    let executables = if profile.test {
+                        &mut build.tests
+                    } else {
+                        &mut build.executables
+                    };
+                    executables.insert(target.name, executable);
+                }
+                Ok(Event::CompilerMessage { target, message }) if message.level == "error" => {
+                    build
+                        .errors
+                        .entry(target.name)
+                        .or_default()
+                        .push_str(&message.rendered.unwrap_or_default());
+                }
+                _ => {}
+            }
+        }
+        build
+    }
+}
+
+/// Message de `cargo build --message-format=json` (seuls les champs utiles sont lus)
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Event {
+    CompilerArtifact {
+        target: Target,
+        #[serde(default)]
+        profile: Profile,
+        executable: Option<PathBuf>,
+    },
+    CompilerMessage {
+        target: Target,
+        message: Diagnostic,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+}
+
+#[derive(Deserialize, Default)]
+struct Profile {
+    test: bool,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    level: String,
+    rendered: Option<String>,
+}
+
+/// Compile des packages en une seule commande `cargo build`.
+///
+/// Avec `--keep-going`, un jour qui ne compile pas n'empêche pas les autres
+/// d'être compilés. Les exécutables et les erreurs sont lus dans la sortie JSON
+/// de cargo. Avec `quiet`, la progression de cargo n'est pas affichée.
+///
+/// # Errors
+///
+/// Retourne une erreur si cargo ne peut pas être lancé.
+pub fn build_packages(packages: &[String], release: bool, quiet: bool) -> Result<BuildOutput> {
+    compile(&[], packages, release, quiet)
+}
+
+/// Compile les tests de packages sans les lancer (`cargo build --tests`).
+///
+/// Contrairement à `cargo test --no-run`, `cargo build` accepte `--keep-going`.
+/// Les exécutables de tests sont dans [`BuildOutput::tests`].
+///
+/// # Errors
+///
+/// Retourne une erreur si cargo ne peut pas être lancé.
+pub fn build_tests(packages: &[String], release: bool, quiet: bool) -> Result<BuildOutput> {
+    compile(&["--tests"], packages, release, quiet)
+}
+
+fn compile(
+    extra_args: &[&str],
+    packages: &[String],
+    release: bool,
+    quiet: bool,
+) -> Result<BuildOutput> {
+    if packages.is_empty() {
+        return Ok(BuildOutput::default());
+    }
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--message-format=json", "--keep-going"])
+        .args(extra_args);
+    for package in packages {
+        command.arg("-p").arg(package);
+    }
+    if release {
+        command.arg("--release");
+    }
+    if quiet {
+        command.arg("--quiet");
+    } else {
+        command.stderr(Stdio::inherit());
+    }
+
+    let start = Instant::now();
+    let output = command
+        .output()
+        .context("Échec de l'exécution de la commande cargo build")?;
+    let mut build = BuildOutput::from_messages(&String::from_utf8_lossy(&output.stdout));
+    build.duration = start.elapsed();
+
+    // Packages non compilés sans diagnostic : dépendance en erreur, package introuvable...
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let fallback = if stderr.trim().is_empty() {
+            "error: compilation impossible, voir la sortie de cargo build".to_string()
+        } else {
+            stderr.into_owned()
+        };
+        for package in packages {
+            if !build.executables.contains_key(package) && !build.tests.contains_key(package) {
+                build
+                    .errors
+                    .entry(package.clone())
+                    .or_insert_with(|| fallback.clone());
+            }
+        }
+    }
+
+    Ok(build)
+}
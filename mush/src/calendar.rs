@@ -0,0 +1,101 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+/// Première année d'Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Dernière année dont le nombre de jours est connu.
+pub const LAST_KNOWN_YEAR: u16 = 2025;
+
+/// Décalage horaire des ouvertures de puzzles : minuit heure de l'Est (UTC-5).
+const UNLOCK_OFFSET_SECS: i32 = 5 * 3600;
+
+/// Description d'une édition d'Advent of Code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub year: u16,
+    /// Nombre de jours de l'édition
+    pub days: u8,
+    /// Fuseau horaire dans lequel les puzzles s'ouvrent à minuit
+    pub unlock_tz: FixedOffset,
+    /// `false` si le nombre de jours est une supposition (année future)
+    pub known: bool,
+}
+
+impl Event {
+    /// Retourne l'édition correspondant à une année.
+    ///
+    /// Les années postérieures à [`LAST_KNOWN_YEAR`] reprennent le format de la
+    /// dernière édition connue et sont marquées comme non confirmées.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'année est antérieure à la première édition.
+    pub fn for_year(year: u16) -> Result<Event> {
+        if year < FIRST_YEAR {
+            bail!(
+                "Advent of Code n'existait pas en {} (première édition : {})",
+                year,
+                FIRST_YEAR
+            );
+        }
+
+        let (days, known) = match year {
+            FIRST_YEAR..=2024 => (25, true),
+            2025..=LAST_KNOWN_YEAR => (12, true),
+            _ => (12, false),
+        };
+
+        Ok(Event {
+            year,
+            days,
+            unlock_tz: FixedOffset::west_opt(UNLOCK_OFFSET_SECS).expect("décalage valide"),
+            known,
+        })
+    }
+
+    /// Message d'avertissement si le nombre de jours de l'édition n'est pas confirmé.
+    pub fn warning(&self) -> Option<String> {
+        (!self.known).then(|| {
+            format!(
+                "Le nombre de jours de l'édition {} n'est pas encore connu, {} jours supposés.",
+                self.year, self.days
+            )
+        })
+    }
+
+    /// Vérifie qu'un jour fait partie de l'édition.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le jour est hors de `1..=days`.
+    pub fn check_day(&self, day: u8) -> Result<()> {
+        if !(1..=self.days).contains(&day) {
+            bail!(
+                "Le jour {} n'existe pas en {} (jours 1 à {})",
+                day,
+                self.year,
+                self.days
+            );
+        }
+        Ok(())
+    }
+
+    /// Date d'ouverture du puzzle d'un jour (minuit le `day` décembre, heure de l'Est).
+    pub fn unlock_time(&self, day: u8) -> DateTime<FixedOffset> {
+        self.unlock_tz
+            .with_ymd_and_hms(i32::from(self.year), 12, u32::from(day), 0, 0, 0)
+            .single()
+            .expect("date d'ouverture valide")
+    }
+
+    /// Indique si le puzzle d'un jour est déjà disponible.
+    pub fn is_unlocked(&self, day: u8) -> bool {
+        Utc::now() >= self.unlock_time(day)
+    }
+
+    /// Pourcentage de jours complétés sur l'édition.
+    pub fn progress(&self, completed: usize) -> f64 {
+        completed as f64 * 100.0 / f64::from(self.days)
+    }
+}
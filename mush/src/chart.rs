@@ -0,0 +1,219 @@
+use crate::results::{PartResult, SavedResults};
+
+/// Couleurs des séries superposées (années ou fichiers de résultats)
+const PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#edc948", "#b07aa1",
+];
+
+const BAR_WIDTH: f64 = 8.0;
+const GROUP_GAP: f64 = 8.0;
+const PLOT_HEIGHT: f64 = 260.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 60.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+
+/// Temps des deux parties de chaque jour pour une source de résultats
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub label: String,
+    /// `(jour, temps partie 1, temps partie 2)` en ms
+    pub days: Vec<(u8, Option<f64>, Option<f64>)>,
+}
+
+impl Series {
+    /// Série des parties ayant répondu avec un temps
+    pub fn from_results(label: impl Into<String>, results: &SavedResults) -> Series {
+        let time = |part: &PartResult| match part {
+            PartResult::Answered {
+                time: Some(time), ..
+            } if !time.is_zero() => Some(time.as_secs_f64() * 1000.0),
+            _ => None,
+        };
+        Series {
+            label: label.into(),
+            days: results
+                .days
+                .iter()
+                .map(|result| (result.day, time(&result.part1), time(&result.part2)))
+                .collect(),
+        }
+    }
+
+    fn times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.days
+            .iter()
+            .flat_map(|(_, part1, part2)| [*part1, *part2])
+            .flatten()
+    }
+}
+
+/// Échelle logarithmique en puissances de 10 entières
+struct LogScale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl LogScale {
+    fn covering(times: impl Iterator<Item = f64>) -> LogScale {
+        let (mut min, mut max) = (f64::MAX, f64::MIN);
+        for time in times {
+            min = min.min(time);
+            max = max.max(time);
+        }
+        if min > max {
+            // Aucune donnée : de 0.001 ms à 1 s
+            return LogScale {
+                min_exp: -3,
+                max_exp: 3,
+            };
+        }
+        // Une décade sous le plus petit temps, même s'il est une puissance de 10 exacte,
+        // pour que sa barre ne soit pas de hauteur nulle
+        let min_exp = min.log10().ceil() as i32 - 1;
+        let max_exp = (max.log10().ceil() as i32).max(min_exp + 1);
+        LogScale { min_exp, max_exp }
+    }
+
+    /// Position d'un temps entre 0 (bas) et 1 (haut)
+    fn ratio(&self, time: f64) -> f64 {
+        let span = f64::from(self.max_exp - self.min_exp);
+        ((time.log10() - f64::from(self.min_exp)) / span).clamp(0.0, 1.0)
+    }
+}
+
+/// Génère un histogramme SVG des temps par jour et par partie, en échelle logarithmique.
+///
+/// Chaque jour regroupe côte à côte les barres de toutes les séries : partie 1
+/// en couleur claire, partie 2 en couleur pleine.
+pub fn render(series: &[Series], title: &str) -> String {
+    let max_day = series
+        .iter()
+        .flat_map(|s| s.days.iter().map(|(day, _, _)| *day))
+        .max()
+        .unwrap_or(1)
+        .max(12);
+    let scale = LogScale::covering(series.iter().flat_map(Series::times));
+
+    let bars_per_day = (series.len() * 2).max(2) as f64;
+    let group_width = bars_per_day * BAR_WIDTH + GROUP_GAP;
+    let width = MARGIN_LEFT + f64::from(max_day) * group_width + MARGIN_RIGHT;
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"11\">\n",
+        w = width,
+        h = height
+    );
+    svg.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>\n",
+        width, height
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"20\" font-size=\"14\" font-weight=\"bold\">{}</text>\n",
+        MARGIN_LEFT,
+        escape(title)
+    ));
+
+    // Légende
+    let mut x = MARGIN_LEFT;
+    for (index, s) in series.iter().enumerate() {
+        let color = PALETTE[index % PALETTE.len()];
+        for (part, opacity) in [(1, 0.5), (2, 1.0)] {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"32\" width=\"10\" height=\"10\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+                x, color, opacity
+            ));
+            let label = format!("{} P{}", s.label, part);
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"41\">{}</text>\n",
+                x + 14.0,
+                escape(&label)
+            ));
+            x += 24.0 + label.chars().count() as f64 * 6.5;
+        }
+    }
+
+    // Graduations : une ligne par puissance de 10
+    for exp in scale.min_exp..=scale.max_exp {
+        let y = bottom - scale.ratio(10f64.powi(exp)) * PLOT_HEIGHT;
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{y:.1}\" x2=\"{}\" y2=\"{y:.1}\" stroke=\"#dddddd\"/>\n",
+            MARGIN_LEFT,
+            width - MARGIN_RIGHT
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n",
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_tick(exp)
+        ));
+    }
+
+    // Barres
+    for day in 1..=max_day {
+        let group_x = MARGIN_LEFT + f64::from(day - 1) * group_width + GROUP_GAP / 2.0;
+        for (index, s) in series.iter().enumerate() {
+            let color = PALETTE[index % PALETTE.len()];
+            let Some((_, part1, part2)) = s.days.iter().find(|(d, _, _)| *d == day) else {
+                continue;
+            };
+            for (offset, (part, time, opacity)) in
+                [(1, part1, 0.5), (2, part2, 1.0)].into_iter().enumerate()
+            {
+                let Some(time) = time else {
+                    continue;
+                };
+                let bar_height = scale.ratio(*time) * PLOT_HEIGHT;
+                let x = group_x + (index * 2 + offset) as f64 * BAR_WIDTH;
+                svg.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{}\" height=\"{:.1}\" fill=\"{}\" fill-opacity=\"{}\"><title>{} · Day {:02} · Partie {} : {:.4} ms</title></rect>\n",
+                    x,
+                    bottom - bar_height,
+                    BAR_WIDTH - 1.0,
+                    bar_height,
+                    color,
+                    opacity,
+                    escape(&s.label),
+                    day,
+                    part,
+                    time
+                ));
+            }
+        }
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            group_x + (group_width - GROUP_GAP) / 2.0,
+            bottom + 16.0,
+            day
+        ));
+    }
+
+    svg.push_str(&format!(
+        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#333333\"/>\n",
+        MARGIN_LEFT,
+        bottom,
+        width - MARGIN_RIGHT,
+        bottom
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Libellé d'une graduation 10^exp ms (ex: `1 µs`, `10 ms`, `1 s`)
+fn format_tick(exp: i32) -> String {
+    match exp {
+        ..=-4 => format!("1e{} ms", exp),
+        -3..=-1 => format!("{} µs", 10f64.powi(exp + 3)),
+        0..=2 => format!("{} ms", 10f64.powi(exp)),
+        _ => format!("{} s", 10f64.powi(exp - 3)),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
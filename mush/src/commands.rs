@@ -1,46 +1,140 @@
 use anyhow::{Context, Result};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command as ShellCommand;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::build;
+use crate::calendar::Event;
+use crate::chart::{self, Series};
+use crate::config::{self, Config};
+use crate::doctor::{self, Severity};
+use crate::examples;
 use crate::fetch::fetch_input;
-use crate::results::{parse_part, DayResult};
-use crate::utils::create_file;
+use crate::inputs::{self, Outcome};
+use crate::libtest::TestReport;
+use crate::manifest;
+use crate::readme;
+use crate::results::{self, Answer, DayResult, DayStatus, Failure, PartResult, SavedResults};
+use crate::runner;
+use crate::utils::{self, create_file};
+use crate::watch::DayWatcher;
+use crate::workspace;
+
+/// Lignes du `.gitignore` généré par `mush init`
+const GITIGNORE_LINES: &[&str] = &[
+    "/target",
+    "**/target",
+    ".env",
+    ".DS_Store",
+    "**/*.rs.bk",
+    "**/input.txt",
+];
 
 /// Initialise le workspace Advent of Code avec les fichiers de configuration nécessaires.
 ///
-/// Cette fonction crée :
+/// Cette fonction crée, ou complète s'ils existent déjà :
 /// - `Cargo.toml` : définition du workspace avec le pattern `solutions/*/*`
 /// - `.gitignore` : fichiers à ignorer dans git
 /// - `.env` : template pour le cookie de session AOC
+/// - `mush.toml` : configuration des scaffolds (dépendances, édition, lints)
+///
+/// Les fichiers existants ne sont jamais écrasés : seuls le membre du workspace,
+/// les lignes du `.gitignore` et les clés du `.env` manquants sont ajoutés. Les
+/// modifications sont affichées sous forme de diff et confirmées avant écriture,
+/// sauf si `assume_yes` est vrai.
 ///
 /// # Errors
 ///
-/// Retourne une erreur si l'écriture des fichiers échoue.
-pub fn initialize_workspace() -> Result<()> {
-    // 1. Créer le fichier Cargo.toml à la racine
-    let cargo_toml_content = r#"[workspace]
+/// Retourne une erreur si un fichier existant est invalide ou si l'écriture échoue.
+pub fn initialize_workspace(assume_yes: bool) -> Result<()> {
+    let cargo_toml_template = format!(
+        r#"[workspace]
 members = [
-    "solutions/*/*"
+    "{}"
 ]
 resolver = "2"
-"#;
-    create_file(&PathBuf::from("Cargo.toml"), cargo_toml_content)?;
-
-    // 2. Créer le fichier .gitignore à la racine
-    let gitignore_content = r#"/target
-**/target
-.env
-.DS_Store
-**/*.rs.bk
-**/input.txt
-"#;
-    create_file(&PathBuf::from(".gitignore"), gitignore_content)?;
+"#,
+        workspace::SOLUTIONS_MEMBER
+    );
+    let contact = if assume_yes { None } else { ask_contact()? };
+    let mut env_keys = vec![("AOC_SESSION", doctor::SESSION_PLACEHOLDER)];
+    if let Some(contact) = &contact {
+        env_keys.push(("AOC_USER_AGENT_CONTACT", contact.as_str()));
+    }
 
-    // 3. Créer le fichier .env à la racine
-    let env_content = r#"AOC_SESSION=your_session_cookie_here
-"#;
-    create_file(&PathBuf::from(".env"), env_content)?;
+    // 1. Calculer le contenu attendu de chaque fichier
+    let mut planned = Vec::new();
+
+    let path = PathBuf::from("Cargo.toml");
+    let existing = read_existing(&path)?;
+    let content = match &existing {
+        Some(content) => workspace::add_workspace_member(content, workspace::SOLUTIONS_MEMBER)
+            .with_context(|| format!("Impossible de compléter {:?}", path))?,
+        None => cargo_toml_template,
+    };
+    planned.push((path, existing, content));
+
+    let path = PathBuf::from(".gitignore");
+    let existing = read_existing(&path)?;
+    let content =
+        workspace::append_missing_lines(existing.as_deref().unwrap_or(""), GITIGNORE_LINES);
+    planned.push((path, existing, content));
+
+    let path = PathBuf::from(".env");
+    let existing = read_existing(&path)?;
+    let mut content = existing.clone().unwrap_or_default();
+    if let Some(contact) = &contact {
+        // Le contact saisi remplace une ligne `AOC_USER_AGENT_CONTACT=` laissée vide
+        content = workspace::fill_empty_env_key(&content, "AOC_USER_AGENT_CONTACT", contact);
+    }
+    let content = workspace::add_env_keys(&content, &env_keys);
+    planned.push((path, existing, content));
+
+    let path = PathBuf::from(config::CONFIG_FILE);
+    let existing = read_existing(&path)?;
+    let content = existing.clone().unwrap_or_else(config::default_config_file);
+    planned.push((path, existing, content));
+
+    let changes: Vec<_> = planned
+        .into_iter()
+        .filter(|(_, existing, content)| existing.as_ref() != Some(content))
+        .collect();
+    if changes.is_empty() {
+        println!("✅ Workspace déjà initialisé, rien à modifier.");
+        return Ok(());
+    }
+
+    // 2. Afficher les modifications et demander confirmation pour les fichiers existants
+    let mut modifies_existing = false;
+    for (path, existing, content) in &changes {
+        match existing {
+            None => println!("➕ {:?} sera créé", path),
+            Some(existing) => {
+                modifies_existing = true;
+                println!("✏️  {:?} sera complété :", path);
+                for line in utils::line_diff(existing, content) {
+                    println!("   {}", line);
+                }
+            }
+        }
+    }
+
+    if modifies_existing && !assume_yes && !utils::confirm("\nAppliquer ces modifications ?")? {
+        println!("❌ Initialisation annulée, aucun fichier modifié.");
+        return Ok(());
+    }
+
+    // 3. Écrire les fichiers
+    for (path, _, content) in &changes {
+        fs::write(path, content)
+            .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    }
 
     println!("✅ Workspace initialisé !");
     println!("👉 N'oublie pas de mettre ton token dans le fichier .env");
@@ -48,14 +142,39 @@ resolver = "2"
     Ok(())
 }
 
+/// Demande le contact à ajouter au User-Agent s'il n'est pas encore configuré.
+///
+/// Rien n'est demandé hors d'un terminal ; une réponse vide passe la question.
+/// Une configuration invalide compte comme une configuration sans contact.
+fn ask_contact() -> Result<Option<String>> {
+    let configured = Config::load().is_ok_and(|config| !config.aoc.contact.trim().is_empty());
+    if !io::stdin().is_terminal() || configured {
+        return Ok(None);
+    }
+    println!("📧 Advent of Code demande un contact dans le User-Agent des outils, pour joindre leur utilisateur en cas de problème.");
+    let contact = utils::prompt("   Votre email ou pseudo (vide pour passer) :")?;
+    Ok((!contact.is_empty()).then_some(contact))
+}
+
+/// Contenu d'un fichier s'il existe
+fn read_existing(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(path)
+        .map(Some)
+        .with_context(|| format!("Impossible de lire le fichier {:?}", path))
+}
+
 /// Crée la structure complète d'un jour de challenge Advent of Code.
 ///
 /// Cette fonction génère :
 /// - L'arborescence de répertoires : `solutions/{year}/day{XX}/src/`
-/// - Le fichier `Cargo.toml` avec les dépendances nécessaires
+/// - Le fichier `Cargo.toml` avec les dépendances et l'édition définies dans `mush.toml`
 /// - Un template `main.rs` avec les fonctions part1/part2 et benchmarking
 /// - Le fichier `input.txt` téléchargé automatiquement depuis adventofcode.com
-/// - Un fichier `example.txt` vide pour les tests
+/// - Un exemple vide `examples/1.txt`, le fichier `examples.toml` de ses réponses attendues
+///   et les tests générés `src/examples.rs`
 ///
 /// # Arguments
 ///
@@ -65,17 +184,20 @@ resolver = "2"
 /// # Errors
 ///
 /// Retourne une erreur si :
+/// - Le jour n'existe pas pour cette année
+/// - Le fichier `mush.toml` est invalide
 /// - La création des répertoires échoue
 /// - L'écriture des fichiers échoue
 /// - Le téléchargement de l'input échoue (mais continue avec un fichier vide)
 pub fn create_scaffold(day: u8, year: u16) -> Result<()> {
+    let event = Event::for_year(year)?;
+    event.check_day(day)?;
+    let config = Config::load()?;
+
     // 1. Définir les chemins
     // Le format {:02} permet d'avoir "day01" au lieu de "day1"
-    let package_name = format!("day{:02}-{}", day, year);
-    let day_str = format!("day{:02}", day);
-    let base_path = PathBuf::from("solutions")
-        .join(year.to_string())
-        .join(&day_str);
+    let package_name = utils::package_name(day, year);
+    let base_path = utils::day_path(day, year);
     let src_path = base_path.join("src");
 
     // 2. Créer les répertoires nécessaires
@@ -84,33 +206,36 @@ pub fn create_scaffold(day: u8, year: u16) -> Result<()> {
 
     // 3. Créer le Cargo.toml du jour
     // On nomme le package day01 pour pouvoir faire "cargo run -p day01" plus tard
-    let cargo_toml_content = format!(
-        r#"[package]
-name = "{}"
-version = "0.1.0"
-edition = "2021"
-
-[dependencies]
-itertools = "0.10.5"
-regex = "1.10.3"
-"#,
-        package_name
-    );
-
+    let cargo_toml_content = manifest::day_manifest(&package_name, &config.scaffold)?;
     create_file(&base_path.join("Cargo.toml"), &cargo_toml_content)?;
+    sync_root_profiles(&config)?;
 
     // 4. Créer le template Rust (main.rs)
     // On prépare la structure pour le benchmak
+    // L'input est lu à l'exécution : 1er argument, sinon AOC_INPUT, sinon input.txt du jour
     let main_rs_content = r#"fn main() {
-    let input = include_str!("../input.txt");
+    let path = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("AOC_INPUT").ok())
+        .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt").to_string());
+    let input = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Impossible de lire l'input {}: {}", path, e));
+    let input = input.as_str();
 
-    let start = std::time::Instant::now();
-    println!("Part 1: {}", part1(input));
-    println!("Time: {:.4}ms", start.elapsed().as_secs_f64() * 1000.0);
+    // Partie à exécuter : AOC_PART=1 ou 2, les deux par défaut
+    let part = std::env::var("AOC_PART").ok();
 
-    let start = std::time::Instant::now();
-    println!("Part 2: {}", part2(input));
-    println!("Time: {:.4}ms", start.elapsed().as_secs_f64() * 1000.0);
+    if part.as_deref() != Some("2") {
+        let start = std::time::Instant::now();
+        println!("Part 1: {}", part1(input));
+        println!("Time: {:?}", start.elapsed());
+    }
+
+    if part.as_deref() != Some("1") {
+        let start = std::time::Instant::now();
+        println!("Part 2: {}", part2(input));
+        println!("Time: {:?}", start.elapsed());
+    }
 }
 
 fn part1(input: &str) -> usize {
@@ -121,16 +246,9 @@ fn part2(input: &str) -> usize {
     0
 }
 
+// Tests des exemples, générés par mush à partir de examples.toml
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1_example() {
-        let example_input = include_str!("../example.txt");
-        assert_eq!(part1(example_input), 0);
-    }
-}
+mod examples;
 "#;
 
     create_file(&src_path.join("main.rs"), main_rs_content)?;
@@ -138,7 +256,15 @@ mod tests {
     // 5. Récupérer et écrire l'input dans input.txt
     let input_path = base_path.join("input.txt");
 
-    if !input_path.exists() || fs::read_to_string(&input_path)?.is_empty() {
+    if !event.is_unlocked(day) {
+        println!(
+            "⏳ Le puzzle du jour {} de l'année {} ouvre le {}, l'input sera à récupérer plus tard.",
+            day,
+            year,
+            event.unlock_time(day).format("%d/%m/%Y à %H:%M (UTC%:z)")
+        );
+        create_file(&input_path, "")?;
+    } else if !input_path.exists() || fs::read_to_string(&input_path)?.is_empty() {
         println!(
             "🌐 Récupération de l'input pour le jour {} de l'année {}...",
             day, year
@@ -161,10 +287,24 @@ mod tests {
         );
     }
 
-    // 6. On créé example.txt vide s'il n'existe pas déjà
-    let example_path = base_path.join("example.txt");
-    if !example_path.exists() {
-        create_file(&example_path, "")?;
+    // 6. Un premier exemple vide, ses réponses attendues et les tests générés
+    // (sauf pour les jours créés avec un unique example.txt, laissés tels quels)
+    if !base_path.join(examples::LEGACY_EXAMPLE).exists() {
+        let example_path = examples::example_path(&base_path, 1);
+        if !example_path.exists() {
+            fs::create_dir_all(base_path.join(examples::EXAMPLES_DIR)).with_context(|| {
+                format!(
+                    "Impossible de créer le répertoire {:?}",
+                    example_path.parent()
+                )
+            })?;
+            create_file(&example_path, "")?;
+        }
+        let examples_file = base_path.join(examples::EXAMPLES_FILE);
+        if !examples_file.exists() {
+            create_file(&examples_file, examples::EXAMPLES_TEMPLATE)?;
+        }
+        examples::sync(&base_path)?;
     }
 
     println!(
@@ -174,79 +314,551 @@ mod tests {
     Ok(())
 }
 
-/// Lance tous les jours d'une année et affiche un bilan global
-pub fn run_all(year: u16, release: bool, summary_only: bool) -> Result<()> {
-    let mut results = Vec::new();
+/// Lance la solution d'un jour avec `cargo run`.
+///
+/// Par défaut la solution lit le `input.txt` du jour ; `input` permet de lui
+/// passer un autre fichier via la variable d'environnement `AOC_INPUT`. Avec
+/// `part`, seule cette partie est exécutée (variable `AOC_PART`).
+///
+/// # Errors
+///
+/// Retourne une erreur si le fichier d'input n'existe pas ou si l'exécution échoue.
+pub fn run_day(
+    day: u8,
+    year: u16,
+    release: bool,
+    part: Option<u8>,
+    input: Option<&Path>,
+) -> Result<()> {
+    let package_name = utils::package_name(day, year);
+    println!(
+        "🚀 Lancement du jour {} de l'année {} (package: {})...",
+        day, year, package_name
+    );
+
+    let day_path = utils::day_path(day, year);
+    if input.is_none() && inputs::ensure_plaintext(&day_path)? {
+        println!("🔓 input.txt déchiffré depuis {}", inputs::ENCRYPTED_INPUT);
+    }
 
-    let max_day = if year >= 2024 { 12 } else { 25 };
+    let mut build = build::build_packages(std::slice::from_ref(&package_name), release, false)?;
+    let Some(executable) = build.executables.remove(&package_name) else {
+        if let Some(errors) = build.errors.get(&package_name) {
+            eprint!("{}", errors);
+        }
+        println!(
+            "❌ Le scaffold pour le jour {} de l'année {} n'a pas été trouvé ou ne compile pas.",
+            day, year
+        );
+        return Err(anyhow::anyhow!(
+            "La compilation du package {} a échoué",
+            package_name
+        ));
+    };
 
-    for day in 1..=max_day {
-        let package_name = format!("day{:02}-{}", day, year);
-        let day_path = PathBuf::from("solutions")
-            .join(year.to_string())
-            .join(format!("day{:02}", day));
+    // Lancer le binaire directement pour mesurer la solution, pas cargo
+    let mut command = ShellCommand::new(executable);
+    if let Some(part) = part {
+        command.env("AOC_PART", part.to_string());
+    }
 
-        // Vérifier si le jour existe
-        if !day_path.exists() {
-            continue;
+    if let Some(input) = input {
+        let input = fs::canonicalize(input)
+            .with_context(|| format!("Le fichier d'input {:?} est introuvable", input))?;
+        println!("📄 Input : {:?}", input);
+        command.env("AOC_INPUT", &input);
+
+        let main_path = day_path.join("src/main.rs");
+        if fs::read_to_string(&main_path)
+            .is_ok_and(|main| main.contains("include_str!(\"../input.txt\")"))
+        {
+            println!(
+                "⚠️  {:?} embarque input.txt avec include_str! et ignorera ce fichier : lisez l'input depuis AOC_INPUT.",
+                main_path
+            );
         }
+    }
+
+    let start = Instant::now();
+    let (status, usage) = runner::run_inherited(&mut command)
+        .with_context(|| format!("Échec de l'exécution du jour {}", day))?;
+    let elapsed = start.elapsed();
+
+    println!(
+        "⏱️  Compilation: {:.2}s | Exécution: {}",
+        build.duration.as_secs_f64(),
+        utils::format_duration(elapsed)
+    );
+    if let Some(usage) = usage {
+        println!(
+            "📈 Mémoire max: {} | CPU: {:.1}ms user, {:.1}ms sys",
+            usage.memory(),
+            usage.user_time_ms,
+            usage.system_time_ms
+        );
+    }
+
+    if !status.success() {
+        println!(
+            "❌ Une erreur est survenue lors de l'exécution du jour {} de l'année {}.",
+            day, year
+        );
+        return Err(anyhow::anyhow!(
+            "La solution a échoué avec le statut {}",
+            status
+        ));
+    }
+
+    Ok(())
+}
+
+/// Surveille un jour et relance ses tests puis la solution à chaque modification.
+///
+/// L'écran est effacé à chaque cycle pour n'afficher que l'état courant :
+/// résultat des tests de l'exemple, puis réponses de la solution.
+///
+/// # Errors
+///
+/// Retourne une erreur si le jour n'existe pas ou si la surveillance échoue.
+pub fn watch_day(day: u8, year: u16, release: bool) -> Result<()> {
+    let day_path = existing_day_path(day, year)?;
+
+    let watcher = DayWatcher::new(&day_path)?;
+    let package_name = utils::package_name(day, year);
+    loop {
+        // Effacer l'écran et replacer le curseur en haut
+        print!("\x1B[2J\x1B[H");
+        println!(
+            "👀 Day {:02} ({}) · {} · Ctrl+C pour quitter",
+            day,
+            year,
+            chrono::Local::now().format("%H:%M:%S")
+        );
+        watch_cycle(&day_path, &package_name, release)?;
+        io::stdout().flush()?;
 
-        // Exécuter le jour
-        let mut command = ShellCommand::new("cargo");
-        command
-            .arg("run")
-            .arg("-p")
-            .arg(&package_name)
-            .arg("--quiet");
-        if release {
-            command.arg("--release");
+        watcher.next_change(None)?;
+    }
+}
+
+/// Un cycle de `watch_day` : tests des exemples, puis solution
+fn watch_cycle(day_path: &Path, package_name: &str, release: bool) -> Result<()> {
+    // examples.toml a pu changer depuis le dernier cycle
+    if let Err(e) = examples::sync(day_path) {
+        println!();
+        print_indented("❌ ", &format!("{:#}", e));
+        return Ok(());
+    }
+    // input.txt a pu être supprimé, seule sa version chiffrée restant
+    match inputs::ensure_plaintext(day_path) {
+        Ok(true) => println!("🔓 input.txt déchiffré depuis {}", inputs::ENCRYPTED_INPUT),
+        Ok(false) => {}
+        Err(e) => {
+            println!();
+            print_indented("❌ ", &format!("{:#}", e));
+            return Ok(());
         }
+    }
 
-        let output = command
-            .output()
-            .with_context(|| format!("Échec de l'exécution du jour {}", day))?;
+    let mut command = ShellCommand::new("cargo");
+    command.arg("test").arg("-p").arg(package_name);
+    if release {
+        command.arg("--release");
+    }
+    let output = command
+        .output()
+        .context("Échec de l'exécution de la commande cargo test")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
 
-        if !output.status.success() {
-            if !summary_only {
-                println!("\n❌ Day {:02}: Erreur d'exécution", day);
-            }
+    match TestReport::parse(&stdout) {
+        None => {
+            // Les tests ne compilent pas : la solution non plus
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            println!();
+            print_indented("❌ ", &Failure::compile(&stderr).describe());
+            return Ok(());
+        }
+        Some(report) if report.failed == 0 => {
+            println!("\n🧪 Tests: ✅ {}/{}", report.passed, report.total());
+        }
+        Some(report) => {
+            println!(
+                "\n🧪 Tests: ❌ {}/{} réussis",
+                report.passed,
+                report.total()
+            );
+            print!("{}", format_test_failures(&report));
+        }
+    }
+
+    let packages = [package_name.to_string()];
+    let mut build = build::build_packages(&packages, release, true)?;
+    println!("\n🎯 Solution:");
+    let Some(executable) = build.executables.remove(package_name) else {
+        let errors = build.errors.remove(package_name).unwrap_or_default();
+        print_indented("  ❌ ", &Failure::compile(&errors).describe());
+        return Ok(());
+    };
+
+    let output = runner::run(&mut ShellCommand::new(executable), None)?;
+    print!(
+        "{}",
+        format_part(1, &PartResult::from_output(&output.stdout, 1, None))
+    );
+    print!(
+        "{}",
+        format_part(2, &PartResult::from_output(&output.stdout, 2, None))
+    );
+    if !output.status.is_some_and(|status| status.success()) {
+        print_indented(
+            "  ❌ ",
+            &Failure::from_exit(output.status, &output.stderr).describe(),
+        );
+    }
+    Ok(())
+}
+
+/// Compile et lance les tests de jours, puis affiche un résumé par jour et un bilan.
+///
+/// Les tests sont compilés en une fois (`cargo build --tests`, qui accepte
+/// `--keep-going` contrairement à `cargo test --no-run`), puis l'exécutable de
+/// tests de chaque jour est lancé directement depuis le répertoire du jour.
+///
+/// # Errors
+///
+/// Retourne une erreur si cargo ne peut pas être lancé, ou si un test échoue ou
+/// ne compile pas (pour un code de sortie exploitable en CI).
+pub fn test_days(year: u16, days: &[u8], release: bool) -> Result<()> {
+    let packages: Vec<String> = days
+        .iter()
+        .map(|day| utils::package_name(*day, year))
+        .collect();
+    let mut build = build::build_tests(&packages, release, true)?;
+
+    let mut totals = TestReport::default();
+    let mut broken = Vec::new();
+    for (day, package) in days.iter().zip(&packages) {
+        let Some(executable) = build.tests.remove(package) else {
+            let errors = build.errors.remove(package).unwrap_or_default();
+            print_indented(
+                &format!("❌ Day {:02}: ", day),
+                &Failure::compile(&errors).describe(),
+            );
+            broken.push(format!("Day {:02}", day));
             continue;
+        };
+
+        let output = ShellCommand::new(&executable)
+            .current_dir(utils::day_path(*day, year))
+            .output()
+            .with_context(|| format!("Échec de l'exécution des tests du jour {}", day))?;
+        let report =
+            TestReport::parse(&String::from_utf8_lossy(&output.stdout)).unwrap_or_default();
+        println!("{}", format_test_summary(*day, &report));
+        print!("{}", format_test_failures(&report));
+        if !output.status.success() && report.failed == 0 {
+            // Les tests se sont arrêtés avant la fin (abort, signal...)
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            print_indented(
+                "  ❌ ",
+                &Failure::from_exit(Some(output.status), &stderr).describe(),
+            );
+            broken.push(format!("Day {:02}", day));
         }
 
-        // Parser la sortie
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let (part1_result, part1_time) = parse_part(&stdout, "Part 1");
-        let (part2_result, part2_time) = parse_part(&stdout, "Part 2");
+        totals.passed += report.passed;
+        totals.failed += report.failed;
+        totals.ignored += report.ignored;
+    }
+
+    println!(
+        "\n📊 Bilan: {} jour(s) · ✅ {} réussi(s) · ❌ {} échoué(s) · {} ignoré(s)",
+        days.len(),
+        totals.passed,
+        totals.failed,
+        totals.ignored
+    );
+    if !broken.is_empty() {
+        println!("  ❌ Sans résultat: {}", broken.join(", "));
+    }
+
+    if totals.failed > 0 || !broken.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{} test(s) en échec, {} jour(s) sans résultat",
+            totals.failed,
+            broken.len()
+        ));
+    }
+    Ok(())
+}
 
-        let day_result = DayResult {
+/// Ligne de résumé des tests d'un jour
+fn format_test_summary(day: u8, report: &TestReport) -> String {
+    let ignored = if report.ignored > 0 {
+        format!(", {} ignoré(s)", report.ignored)
+    } else {
+        String::new()
+    };
+    if report.total() == 0 {
+        format!("⚪ Day {:02}: aucun test{}", day, ignored)
+    } else if report.failed == 0 {
+        format!(
+            "✅ Day {:02}: {}/{} réussi(s){}",
+            day,
+            report.passed,
+            report.total(),
+            ignored
+        )
+    } else {
+        format!(
+            "❌ Day {:02}: {}/{} réussi(s){}",
             day,
-            part1_result,
-            part1_time,
-            part2_result,
-            part2_time,
+            report.passed,
+            report.total(),
+            ignored
+        )
+    }
+}
+
+/// Tests en échec et leur sortie (message d'assertion, valeurs comparées...)
+fn format_test_failures(report: &TestReport) -> String {
+    let mut text = String::new();
+    for (name, output) in &report.failures {
+        text.push_str(&format!("  ✗ {}\n", name));
+        for line in output.lines() {
+            text.push_str(&format!("      {}\n", line));
+        }
+    }
+    text
+}
+
+/// Affiche un texte sur plusieurs lignes, la première précédée de `prefix`
+fn print_indented(prefix: &str, text: &str) {
+    for (index, line) in text.lines().enumerate() {
+        if index == 0 {
+            println!("{}{}", prefix, line);
+        } else {
+            println!("{:width$}{}", "", line, width = prefix.chars().count() + 1);
+        }
+    }
+}
+
+/// Aligne les `Cargo.toml` de tous les jours existants sur la section `[scaffold]` de `mush.toml`.
+///
+/// Met à jour l'édition, les dépendances et les lints de chaque jour (en retirant ceux qui ne
+/// sont plus configurés), ainsi que les profils du `Cargo.toml` racine.
+///
+/// # Errors
+///
+/// Retourne une erreur si la configuration ou un manifeste est invalide, ou si
+/// l'écriture d'un fichier échoue.
+pub fn sync_dependencies() -> Result<()> {
+    let config = Config::load()?;
+    let mut updated = 0;
+
+    for (_, _, day_path) in utils::scaffolded_days()? {
+        let manifest_path = day_path.join("Cargo.toml");
+        if !manifest_path.exists() {
+            continue;
+        }
+
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Impossible de lire le fichier {:?}", manifest_path))?;
+        let synced = manifest::sync_day_manifest(&content, &config.scaffold)
+            .with_context(|| format!("Impossible de mettre à jour {:?}", manifest_path))?;
+
+        if synced != content {
+            fs::write(&manifest_path, synced).with_context(|| {
+                format!("Impossible d'écrire dans le fichier {:?}", manifest_path)
+            })?;
+            println!("✅ {:?} mis à jour", manifest_path);
+            updated += 1;
+        }
+    }
+
+    sync_root_profiles(&config)?;
+
+    if updated == 0 {
+        println!("✅ Tous les jours sont déjà à jour");
+    } else {
+        println!("✅ {} jour(s) mis à jour", updated);
+    }
+
+    Ok(())
+}
+
+/// Reporte les profils de `mush.toml` dans le `Cargo.toml` racine s'il existe
+fn sync_root_profiles(config: &Config) -> Result<()> {
+    let root_manifest = PathBuf::from("Cargo.toml");
+    if config.scaffold.profile.is_empty() || !root_manifest.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&root_manifest)
+        .with_context(|| format!("Impossible de lire le fichier {:?}", root_manifest))?;
+    let synced = manifest::sync_root_profiles(&content, &config.scaffold)?;
+    if synced != content {
+        fs::write(&root_manifest, synced)
+            .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", root_manifest))?;
+        println!("✅ Profils mis à jour dans {:?}", root_manifest);
+    }
+
+    Ok(())
+}
+
+/// Diagnostique le workspace et affiche chaque problème avec une suggestion.
+///
+/// Avec `fix`, applique les corrections sans risque (membres du workspace,
+/// `.gitignore`, noms de packages).
+///
+/// # Errors
+///
+/// Retourne une erreur si une correction échoue ou s'il reste des problèmes bloquants.
+pub fn doctor(fix: bool) -> Result<()> {
+    let findings = doctor::diagnose()?;
+    if findings.is_empty() {
+        println!("✅ Aucun problème détecté");
+        return Ok(());
+    }
+
+    let mut remaining_errors = 0;
+    let mut warnings = 0;
+    let mut fixable = 0;
+    for finding in &findings {
+        let icon = match finding.severity {
+            Severity::Error => "❌",
+            Severity::Warning => "⚠️ ",
         };
+        println!("\n{} {}", icon, finding.message);
+        println!("   👉 {}", finding.suggestion);
 
-        // Afficher le résultat du jour si pas en mode summary_only
-        if !summary_only {
-            println!("\nDay {:02}:", day);
-            if let Some(r) = &day_result.part1_result {
-                print!("  Part 1: {}", r);
-                if let Some(t) = day_result.part1_time {
-                    print!(" ({:.4}ms)", t);
-                }
-                println!();
+        let fixed = match (&finding.fix, fix) {
+            (Some(correction), true) => {
+                doctor::apply(correction)?;
+                println!("   🔧 Corrigé");
+                true
             }
-            if let Some(r) = &day_result.part2_result {
-                print!("  Part 2: {}", r);
-                if let Some(t) = day_result.part2_time {
-                    print!(" ({:.4}ms)", t);
-                }
-                println!();
+            (Some(_), false) => {
+                println!("   🔧 Corrigeable automatiquement avec --fix");
+                fixable += 1;
+                false
             }
-            println!("  Total: {:.4}ms", day_result.total_time());
+            (None, _) => false,
+        };
+
+        match finding.severity {
+            Severity::Error if !fixed => remaining_errors += 1,
+            Severity::Warning if !fixed => warnings += 1,
+            _ => {}
+        }
+    }
+
+    println!(
+        "\n📊 {} erreur(s), {} avertissement(s)",
+        remaining_errors, warnings
+    );
+    if fixable > 0 {
+        println!(
+            "👉 Relancez `mush doctor --fix` pour corriger {} problème(s)",
+            fixable
+        );
+    }
+
+    if remaining_errors > 0 {
+        return Err(anyhow::anyhow!(
+            "{} problème(s) bloquant(s) dans le workspace",
+            remaining_errors
+        ));
+    }
+    Ok(())
+}
+
+/// Options de `run_all`
+#[derive(Debug, Clone, Copy)]
+pub struct RunAllOptions {
+    /// Lance en mode release
+    pub release: bool,
+    /// N'affiche que le bilan final
+    pub summary_only: bool,
+    /// N'exécute que cette partie (1 ou 2)
+    pub part: Option<u8>,
+    /// Délai maximal de chaque partie, au-delà duquel le jour est interrompu
+    pub timeout: Option<Duration>,
+    /// Nombre de jours exécutés en parallèle
+    pub jobs: usize,
+    /// Affiche les résultats en JSON au lieu du détail et du bilan
+    pub json: bool,
+    /// Affiche la sortie d'erreur complète des jours en échec
+    pub verbose: bool,
+}
+
+impl Default for RunAllOptions {
+    fn default() -> Self {
+        RunAllOptions {
+            release: false,
+            summary_only: false,
+            part: None,
+            timeout: None,
+            jobs: 1,
+            json: false,
+            verbose: false,
+        }
+    }
+}
+
+/// Lance tous les jours d'une année et affiche un bilan global.
+///
+/// Les résultats sont aussi enregistrés pour `mush tui` (voir [`SavedResults`]).
+pub fn run_all(year: u16, options: RunAllOptions) -> Result<()> {
+    let RunAllOptions {
+        release,
+        summary_only,
+        jobs,
+        json,
+        ..
+    } = options;
+    let summary_only = summary_only || json;
+
+    let event = Event::for_year(year)?;
+    let max_day = event.days;
+    let days: Vec<u8> = (1..=max_day)
+        .filter(|day| utils::day_path(*day, year).exists())
+        .collect();
+
+    if jobs > 1 && !json {
+        println!(
+            "⚠️  {} jours exécutés en parallèle : les temps mesurés sont moins fiables qu'en série.",
+            jobs
+        );
+    }
+
+    for day in &days {
+        match inputs::ensure_plaintext(&utils::day_path(*day, year)) {
+            Ok(true) if !json => println!("🔓 Day {:02}: input.txt déchiffré", day),
+            Err(e) if !json => println!("⚠️  Day {:02}: {:#}", day, e),
+            _ => {}
+        }
+    }
+
+    let (results, build_time) = collect_results(year, &days, options, |day_result| {
+        if !summary_only {
+            print!("{}", format_day(day_result, options));
         }
+    })?;
 
-        results.push(day_result);
+    let mut saved = SavedResults::load(year)?;
+    saved.merge(&results);
+    saved.save()?;
+
+    if json {
+        let report = RunReport {
+            year,
+            release,
+            build_time_ms: build_time.as_secs_f64() * 1000.0,
+            days: &results,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
     }
 
     // Afficher le bilan global
@@ -255,34 +867,713 @@ pub fn run_all(year: u16, release: bool, summary_only: bool) -> Result<()> {
         return Ok(());
     }
 
-    let total_time: f64 = results.iter().map(|r| r.total_time()).sum();
-    let avg_time = total_time / results.len() as f64;
-    let fastest = results
+    let completed: Vec<&DayResult> = results.iter().filter(|r| r.is_ok()).collect();
+    let mode = if release { " (mode release)" } else { "" };
+    println!("\n📊 Bilan global{}:", mode);
+    println!(
+        "  Jours complétés: {}/{} ({:.0}%)",
+        completed.len(),
+        max_day,
+        event.progress(completed.len())
+    );
+    println!("  Compilation: {:.2}s", build_time.as_secs_f64());
+
+    if !completed.is_empty() {
+        let total_time: Duration = completed.iter().map(|r| r.total_time()).sum();
+        let avg_time = total_time / completed.len() as u32;
+        let fastest = completed.iter().min_by_key(|r| r.total_time());
+        let slowest = completed.iter().max_by_key(|r| r.total_time());
+
+        let hungriest = completed
+            .iter()
+            .filter_map(|r| Some((r, r.usage?)))
+            .max_by_key(|(_, usage)| usage.max_rss_kb);
+
+        println!("  Temps total: {}", utils::format_duration(total_time));
+        println!("  Temps moyen: {}/jour", utils::format_duration(avg_time));
+        let run_time: Duration = completed.iter().filter_map(|r| r.run_time).sum();
+        println!(
+            "  Temps d'exécution des processus: {}",
+            utils::format_duration(run_time)
+        );
+        let cpu_time: f64 = completed
+            .iter()
+            .filter_map(|r| r.usage)
+            .map(|usage| usage.user_time_ms + usage.system_time_ms)
+            .sum();
+        if cpu_time > 0.0 {
+            println!("  Temps CPU total: {:.1}ms", cpu_time);
+        }
+        if let Some(f) = fastest {
+            println!(
+                "  Jour le plus rapide: Day {:02} ({}{})",
+                f.day,
+                utils::format_duration(f.total_time()),
+                memory_suffix(f)
+            );
+        }
+        if let Some(s) = slowest {
+            println!(
+                "  Jour le plus lent: Day {:02} ({}{})",
+                s.day,
+                utils::format_duration(s.total_time()),
+                memory_suffix(s)
+            );
+        }
+        if let Some((h, usage)) = hungriest {
+            println!(
+                "  Jour le plus gourmand en mémoire: Day {:02} ({})",
+                h.day,
+                usage.memory()
+            );
+        }
+    }
+
+    let timed_out: Vec<String> = results
         .iter()
-        .min_by(|a, b| a.total_time().partial_cmp(&b.total_time()).unwrap());
-    let slowest = results
+        .filter_map(|r| match r.status {
+            DayStatus::TimedOut { part } => Some(format!("Day {:02} (partie {})", r.day, part)),
+            _ => None,
+        })
+        .collect();
+    if !timed_out.is_empty() {
+        println!("  ⏱️  Timeout: {}", timed_out.join(", "));
+    }
+
+    let failed: Vec<String> = results
         .iter()
-        .max_by(|a, b| a.total_time().partial_cmp(&b.total_time()).unwrap());
+        .filter_map(|r| match &r.status {
+            DayStatus::Failed { failure, .. } => {
+                Some(format!("Day {:02} ({})", r.day, failure.label()))
+            }
+            _ => None,
+        })
+        .collect();
+    if !failed.is_empty() {
+        println!("  ❌ En erreur: {}", failed.join(", "));
+    }
 
-    let mode = if release { " (mode release)" } else { "" };
-    println!("\n📊 Bilan global{}:", mode);
-    println!("  Jours complétés: {}/{}", results.len(), max_day);
-    println!("  Temps total: {:.4}ms", total_time);
-    println!("  Temps moyen: {:.4}ms/jour", avg_time);
-    if let Some(f) = fastest {
+    Ok(())
+}
+
+/// Met à jour le tableau de progression entre les marqueurs `mush:start` et `mush:end` d'un README.
+///
+/// Un tableau est généré par année ayant des jours créés (ou pour `year` seule),
+/// à partir des derniers résultats enregistrés. Avec `run`, les jours sont
+/// d'abord exécutés comme avec `run-all`.
+///
+/// # Errors
+///
+/// Retourne une erreur si une exécution échoue ou si le README ne peut pas être écrit.
+pub fn update_readme(year: Option<u16>, run: bool, release: bool, path: &Path) -> Result<()> {
+    let scaffolded = utils::scaffolded_days()?;
+    let mut years: Vec<u16> = match year {
+        Some(year) => vec![year],
+        None => scaffolded.iter().map(|(year, _, _)| *year).collect(),
+    };
+    // Années les plus récentes en premier
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+
+    let mut sections = Vec::new();
+    for year in years {
+        let days: Vec<u8> = scaffolded
+            .iter()
+            .filter(|(y, _, _)| *y == year)
+            .map(|(_, day, _)| *day)
+            .collect();
+        let mut saved = SavedResults::load(year)?;
+        if run {
+            println!("🎄 Exécution des jours de {}...", year);
+            let options = RunAllOptions {
+                release,
+                ..RunAllOptions::default()
+            };
+            let (results, _) = collect_results(year, &days, options, |_| {})?;
+            saved.merge(&results);
+            saved.save()?;
+        } else if saved.days.is_empty() && !days.is_empty() {
+            println!(
+                "⚠️  Aucun résultat enregistré pour {} dans {:?} (supprimé par `cargo clean` ?) : lancez `mush readme --run` ou `mush run-all`.",
+                year,
+                SavedResults::path(year)
+            );
+        }
+        sections.push(readme::render_year(&saved, &days));
+    }
+
+    let content = read_existing(path)?.unwrap_or_default();
+    let updated = readme::replace_section(&content, &sections.join("\n"));
+    fs::write(path, updated)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    println!(
+        "✅ Tableau de progression mis à jour dans {:?} ({} année(s))",
+        path,
+        sections.len()
+    );
+    Ok(())
+}
+
+/// Ajoute un exemple à un jour, lu sur l'entrée standard ou dans le presse-papiers.
+///
+/// # Errors
+///
+/// Retourne une erreur si le jour n'existe pas, si l'exemple est vide ou si l'écriture échoue.
+pub fn add_example(
+    day: u8,
+    year: u16,
+    part1: Option<&str>,
+    part2: Option<&str>,
+    clipboard: bool,
+) -> Result<()> {
+    let day_path = existing_day_path(day, year)?;
+    let content = if clipboard {
+        examples::read_clipboard()?
+    } else {
+        if io::stdin().is_terminal() {
+            println!("📋 Collez l'exemple puis validez avec Ctrl+D :");
+        }
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Impossible de lire l'entrée standard")?;
+        content
+    };
+    if content.trim().is_empty() {
+        return Err(anyhow::anyhow!("L'exemple est vide"));
+    }
+
+    let number = examples::add(&day_path, &content, part1, part2)?;
+    println!(
+        "✅ Exemple {} ajouté dans {:?}",
+        number,
+        examples::example_path(&day_path, number)
+    );
+    if part1.is_none() && part2.is_none() {
         println!(
-            "  Jour le plus rapide: Day {:02} ({:.4}ms)",
-            f.day,
-            f.total_time()
+            "💡 Ajoutez ses réponses attendues dans {:?} puis lancez `mush example sync -d {} -y {}`",
+            day_path.join(examples::EXAMPLES_FILE),
+            day,
+            year
         );
     }
-    if let Some(s) = slowest {
+    warn_undeclared_examples(&day_path);
+    Ok(())
+}
+
+/// Régénère les tests des exemples d'un jour à partir de `examples.toml`.
+///
+/// # Errors
+///
+/// Retourne une erreur si le jour n'existe pas ou si `examples.toml` est invalide.
+pub fn sync_examples(day: u8, year: u16) -> Result<()> {
+    let day_path = existing_day_path(day, year)?;
+    let count = examples::load(&day_path)?.len();
+    if examples::sync(&day_path)? {
+        println!(
+            "✅ {:?} régénéré ({} exemple(s))",
+            day_path.join(examples::TESTS_FILE),
+            count
+        );
+    } else {
+        println!("✅ Les tests des exemples sont à jour");
+    }
+    warn_undeclared_examples(&day_path);
+    Ok(())
+}
+
+fn warn_undeclared_examples(day_path: &Path) {
+    if !examples::is_declared(day_path) {
         println!(
-            "  Jour le plus lent: Day {:02} ({:.4}ms)",
-            s.day,
-            s.total_time()
+            "⚠️  {:?} n'inclut pas les tests générés : ajoutez `#[cfg(test)] mod examples;`",
+            day_path.join("src/main.rs")
         );
     }
+}
+
+/// Répertoire d'un jour, ou une erreur invitant à le créer
+fn existing_day_path(day: u8, year: u16) -> Result<PathBuf> {
+    let day_path = utils::day_path(day, year);
+    if !day_path.exists() {
+        return Err(anyhow::anyhow!(
+            "Le jour {} de l'année {} n'existe pas : lancez `mush scaffold -d {} -y {}`",
+            day,
+            year,
+            day,
+            year
+        ));
+    }
+    Ok(day_path)
+}
 
+/// Écrit l'histogramme SVG des temps des années et fichiers de résultats donnés.
+///
+/// # Errors
+///
+/// Retourne une erreur si un fichier de résultats est illisible ou si le SVG ne peut pas être écrit.
+pub fn chart(years: &[u16], files: &[PathBuf], output: &Path) -> Result<()> {
+    let mut series = Vec::new();
+    for year in years {
+        let saved = SavedResults::load(*year)?;
+        if saved.days.is_empty() {
+            println!(
+                "⚠️  Aucun résultat enregistré pour {} (lancez `mush run-all -y {}`)",
+                year, year
+            );
+        }
+        series.push(Series::from_results(year.to_string(), &saved));
+    }
+    for file in files {
+        let saved = SavedResults::read(file)?;
+        let label = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| saved.year.to_string());
+        series.push(Series::from_results(label, &saved));
+    }
+
+    let labels: Vec<&str> = series.iter().map(|s| s.label.as_str()).collect();
+    let title = format!("Temps par jour ({})", labels.join(", "));
+    fs::write(output, chart::render(&series, &title))
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", output))?;
+    println!(
+        "📊 Histogramme écrit dans {:?} ({} série(s))",
+        output,
+        series.len()
+    );
+    Ok(())
+}
+
+/// Génère la clé age des inputs et affiche sa clé publique.
+///
+/// # Errors
+///
+/// Retourne une erreur si la clé existe déjà ou ne peut pas être écrite.
+pub fn generate_inputs_key(output: Option<&Path>) -> Result<()> {
+    let path = match output {
+        Some(path) => path.to_path_buf(),
+        None => inputs::key_path(&Config::load()?.inputs)
+            .context("Impossible de déterminer le fichier de la clé : utilisez --output")?,
+    };
+    let recipient = inputs::generate_key(&path)?;
+    println!("🔑 Clé écrite dans {:?}", path);
+    println!("   Clé publique : {}", recipient);
+    println!(
+        "👉 Sauvegardez cette clé : sans elle, les input.txt.age sont illisibles. En CI, passez son contenu dans {}.",
+        inputs::KEY_ENV
+    );
     Ok(())
 }
+
+/// Chiffre le `input.txt` des jours sélectionnés dans `input.txt.age`.
+///
+/// # Errors
+///
+/// Retourne une erreur si la clé est introuvable ou si un chiffrement échoue.
+pub fn encrypt_inputs(year: Option<u16>, day: Option<u8>, force: bool) -> Result<()> {
+    let config = Config::load()?;
+    let identity = inputs::load_identity(&config.inputs)?;
+    let recipients = inputs::recipients(&identity, &config.inputs)?;
+
+    let mut counts = BTreeMap::new();
+    for (year, day, path) in selected_days(year, day)? {
+        let outcome = inputs::encrypt_day(&path, &identity, &recipients, force)?;
+        match outcome {
+            Outcome::Written => println!("🔒 Day {:02} ({}): input.txt.age écrit", day, year),
+            Outcome::Conflict => println!(
+                "⚠️  Day {:02} ({}): input.txt.age est chiffré avec une autre clé, --force pour le remplacer",
+                day, year
+            ),
+            Outcome::Unchanged | Outcome::Missing | Outcome::Corrupted => {}
+        }
+        *counts.entry(outcome_label(outcome)).or_insert(0) += 1;
+    }
+    print_outcomes("🔒 Chiffrement", &counts);
+    Ok(())
+}
+
+/// Déchiffre le `input.txt.age` des jours sélectionnés dans `input.txt`.
+///
+/// # Errors
+///
+/// Retourne une erreur si la clé est introuvable ou si un déchiffrement échoue.
+pub fn decrypt_inputs(year: Option<u16>, day: Option<u8>, force: bool) -> Result<()> {
+    let identity = inputs::load_identity(&Config::load()?.inputs)?;
+
+    let mut counts = BTreeMap::new();
+    for (year, day, path) in selected_days(year, day)? {
+        let outcome = inputs::decrypt_day(&path, &identity, force)?;
+        match outcome {
+            Outcome::Written => println!("🔓 Day {:02} ({}): input.txt écrit", day, year),
+            Outcome::Conflict => println!(
+                "⚠️  Day {:02} ({}): input.txt diffère de input.txt.age, --force pour le remplacer",
+                day, year
+            ),
+            Outcome::Unchanged | Outcome::Missing | Outcome::Corrupted => {}
+        }
+        *counts.entry(outcome_label(outcome)).or_insert(0) += 1;
+    }
+    print_outcomes("🔓 Déchiffrement", &counts);
+    Ok(())
+}
+
+/// Sens d'une synchronisation avec le répertoire partagé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Du workspace vers le répertoire partagé
+    Push,
+    /// Du répertoire partagé vers le workspace
+    Pull,
+}
+
+/// Copie les `input.txt` des jours sélectionnés vers ou depuis un répertoire partagé.
+///
+/// Les inputs y sont rangés par année et par jour (`<dir>/2024/day01/input.txt`), avec
+/// leur somme SHA-256 vérifiée à chaque copie. Les fichiers différents des deux côtés
+/// ne sont remplacés qu'avec `force`.
+///
+/// # Errors
+///
+/// Retourne une erreur si aucun répertoire n'est configuré, si une copie échoue, ou
+/// si des conflits ou des inputs corrompus ont été trouvés.
+pub fn sync_inputs(
+    direction: SyncDirection,
+    dir: Option<&Path>,
+    year: Option<u16>,
+    day: Option<u8>,
+    force: bool,
+) -> Result<()> {
+    let dir = inputs::shared_dir(dir, &Config::load()?.inputs)?;
+    if direction == SyncDirection::Pull && !dir.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Le répertoire partagé {:?} est introuvable",
+            dir
+        ));
+    }
+
+    let mut counts = BTreeMap::new();
+    let mut problems = 0;
+    let selected = selected_days(year, day)?;
+    for (year, day, path) in &selected {
+        let (year, day) = (*year, *day);
+        let shared_day = inputs::shared_day_path(&dir, year, day);
+        let outcome = match direction {
+            SyncDirection::Push => inputs::push_day(path, &shared_day, force)?,
+            SyncDirection::Pull => inputs::pull_day(&shared_day, path, force)?,
+        };
+        match (direction, outcome) {
+            (SyncDirection::Push, Outcome::Written) => {
+                println!("📤 Day {:02} ({}): input.txt copié", day, year)
+            }
+            (SyncDirection::Pull, Outcome::Written) => {
+                println!("📥 Day {:02} ({}): input.txt copié", day, year)
+            }
+            (SyncDirection::Push, Outcome::Conflict) => println!(
+                "⚠️  Day {:02} ({}): l'input partagé diffère de input.txt, --force pour le remplacer",
+                day, year
+            ),
+            (SyncDirection::Pull, Outcome::Conflict) => println!(
+                "⚠️  Day {:02} ({}): input.txt diffère de l'input partagé, --force pour le remplacer",
+                day, year
+            ),
+            (_, Outcome::Corrupted) => println!(
+                "❌ Day {:02} ({}): {:?} ne correspond pas à sa somme {}",
+                day,
+                year,
+                shared_day.join(inputs::INPUT_FILE),
+                inputs::CHECKSUM_FILE
+            ),
+            (_, Outcome::Unchanged | Outcome::Missing) => {}
+        }
+        if matches!(outcome, Outcome::Conflict | Outcome::Corrupted) {
+            problems += 1;
+        }
+        *counts.entry(outcome_label(outcome)).or_insert(0) += 1;
+    }
+
+    // Les inputs partagés des jours absents ne sont pas copiés : il faut d'abord créer le jour
+    if direction == SyncDirection::Pull {
+        for (shared_year, shared_day) in inputs::shared_days(&dir)? {
+            let wanted = year.is_none_or(|year| year == shared_year)
+                && day.is_none_or(|day| day == shared_day);
+            let local = selected
+                .iter()
+                .any(|(y, d, _)| (*y, *d) == (shared_year, shared_day));
+            if wanted && !local {
+                println!(
+                    "⏭️  Day {:02} ({}): input partagé mais jour non créé, lancez `mush scaffold -d {} -y {}` puis relancez pull",
+                    shared_day, shared_year, shared_day, shared_year
+                );
+                *counts.entry("sans jour local").or_insert(0) += 1;
+            }
+        }
+    }
+
+    let title = match direction {
+        SyncDirection::Push => format!("📤 Envoi vers {:?}", dir),
+        SyncDirection::Pull => format!("📥 Récupération depuis {:?}", dir),
+    };
+    print_outcomes(&title, &counts);
+    if problems > 0 {
+        return Err(anyhow::anyhow!(
+            "{} input(s) non synchronisé(s) : conflit ou somme de contrôle invalide",
+            problems
+        ));
+    }
+    Ok(())
+}
+
+/// Jours créés, filtrés par année et par jour
+fn selected_days(year: Option<u16>, day: Option<u8>) -> Result<Vec<(u16, u8, PathBuf)>> {
+    let days: Vec<_> = utils::scaffolded_days()?
+        .into_iter()
+        .filter(|(y, d, _)| year.is_none_or(|year| year == *y) && day.is_none_or(|day| day == *d))
+        .collect();
+    if days.is_empty() {
+        println!("📊 Aucun jour trouvé");
+    }
+    Ok(days)
+}
+
+fn outcome_label(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Written => "écrit(s)",
+        Outcome::Unchanged => "à jour",
+        Outcome::Missing => "sans fichier source",
+        Outcome::Conflict => "en conflit",
+        Outcome::Corrupted => "corrompu(s)",
+    }
+}
+
+fn print_outcomes(title: &str, counts: &BTreeMap<&str, usize>) {
+    if counts.is_empty() {
+        return;
+    }
+    let parts: Vec<String> = counts
+        .iter()
+        .map(|(label, count)| format!("{} {}", count, label))
+        .collect();
+    println!("{}: {}", title, parts.join(" · "));
+}
+
+/// Compile des jours puis les exécute, et retourne leurs résultats dans l'ordre des jours.
+///
+/// Les jours sont compilés en une fois, puis leurs binaires sont exécutés par
+/// `options.jobs` workers. `on_result` est appelé pour chaque jour dans l'ordre
+/// des jours, quel que soit l'ordre de fin d'exécution. La durée de compilation
+/// est retournée avec les résultats.
+///
+/// # Errors
+///
+/// Retourne une erreur si cargo ou un binaire ne peut pas être lancé.
+pub fn collect_results(
+    year: u16,
+    days: &[u8],
+    options: RunAllOptions,
+    mut on_result: impl FnMut(&DayResult),
+) -> Result<(Vec<DayResult>, Duration)> {
+    // Compiler d'abord, pour que le délai ne porte que sur l'exécution
+    let packages: Vec<String> = days
+        .iter()
+        .map(|day| utils::package_name(*day, year))
+        .collect();
+    let build = build::build_packages(&packages, options.release, true)?;
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let results = thread::scope(|scope| -> Result<Vec<DayResult>> {
+        for _ in 0..options.jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let (next, build) = (&next, &build);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+                let package_name = utils::package_name(day, year);
+                let result = match build.executables.get(&package_name) {
+                    Some(executable) => run_binary(executable, day, options),
+                    None => Ok(DayResult::compile_error(
+                        day,
+                        options.release,
+                        build.errors.get(&package_name).cloned().unwrap_or_default(),
+                    )),
+                };
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // Les jours terminés en avance attendent leur tour
+        let mut pending = BTreeMap::new();
+        let mut results = Vec::new();
+        for (index, result) in receiver {
+            pending.insert(index, result?);
+            while let Some(day_result) = pending.remove(&results.len()) {
+                on_result(&day_result);
+                results.push(day_result);
+            }
+        }
+        Ok(results)
+    })?;
+
+    Ok((results, build.duration))
+}
+
+/// Résultats de `run_all --json`
+#[derive(serde::Serialize)]
+struct RunReport<'a> {
+    year: u16,
+    release: bool,
+    /// Durée de compilation de l'année
+    build_time_ms: f64,
+    days: &'a [DayResult],
+}
+
+/// Pic mémoire à ajouter au temps d'un jour dans le bilan (ex: `, 12.3 Mo`)
+fn memory_suffix(result: &DayResult) -> String {
+    result
+        .usage
+        .map(|usage| format!(", {}", usage.memory()))
+        .unwrap_or_default()
+}
+
+/// Exécute le binaire déjà compilé d'un jour et analyse sa sortie
+fn run_binary(executable: &Path, day: u8, options: RunAllOptions) -> Result<DayResult> {
+    let mut command = ShellCommand::new(executable);
+    if let Some(part) = options.part {
+        command.env("AOC_PART", part.to_string());
+    }
+    let output = runner::run(&mut command, options.timeout)
+        .with_context(|| format!("Échec de l'exécution du jour {}", day))?;
+
+    let part1 = PartResult::from_output(&output.stdout, 1, options.part);
+    let part2 = PartResult::from_output(&output.stdout, 2, options.part);
+    if let Some(selected) = options.part {
+        let other = if selected == 1 { &part2 } else { &part1 };
+        if matches!(other, PartResult::Answered { .. }) {
+            eprintln!(
+                "⚠️  Le jour {} ignore AOC_PART et exécute aussi la partie {} : lisez AOC_PART pour n'exécuter que la partie demandée.",
+                day,
+                3 - selected
+            );
+        }
+    }
+    let status = if output.timed_out {
+        DayStatus::TimedOut {
+            part: if part1 == PartResult::Missing { 1 } else { 2 },
+        }
+    } else if output.status.is_some_and(|status| status.success()) {
+        DayStatus::Ok
+    } else {
+        DayStatus::Failed {
+            failure: Failure::from_exit(output.status, &output.stderr),
+            stderr: output.stderr,
+        }
+    };
+    Ok(DayResult {
+        day,
+        status,
+        part1,
+        part2,
+        usage: output.usage,
+        run_time: Some(output.elapsed),
+        release: options.release,
+    })
+}
+
+/// Détail d'un jour affiché par `run_all`
+fn format_day(result: &DayResult, options: RunAllOptions) -> String {
+    let day = result.day;
+    match &result.status {
+        DayStatus::Ok => format!(
+            "\nDay {:02}:\n{}{}  Total: {} (processus: {})\n{}",
+            day,
+            format_part(1, &result.part1),
+            format_part(2, &result.part2),
+            utils::format_duration(result.total_time()),
+            utils::format_duration(result.run_time.unwrap_or_default()),
+            format_usage(result)
+        ),
+        DayStatus::Failed { failure, stderr } => {
+            let mut text = format!("\n❌ Day {:02}: ", day);
+            for (index, line) in failure.describe().lines().enumerate() {
+                if index > 0 {
+                    text.push_str("  ");
+                }
+                text.push_str(line);
+                text.push('\n');
+            }
+            // Les réponses affichées avant l'échec restent utiles
+            for (part, part_result) in [(1, &result.part1), (2, &result.part2)] {
+                if matches!(part_result, PartResult::Answered { .. }) {
+                    text.push_str(&format_part(part, part_result));
+                }
+            }
+            if options.verbose {
+                text.push_str("  --- stderr ---\n");
+                for line in stderr.lines() {
+                    text.push_str(&format!("  {}\n", line));
+                }
+            } else if matches!(failure, Failure::ExitCode { .. } | Failure::Signal { .. }) {
+                for line in results::last_lines(stderr, 3) {
+                    text.push_str(&format!("  {}\n", line));
+                }
+            }
+            text
+        }
+        DayStatus::TimedOut { part } => {
+            let mut text = format!(
+                "\n⏱️  Day {:02}: Partie {} interrompue après {:?}\n",
+                day,
+                part,
+                options.timeout.unwrap_or_default()
+            );
+            if *part == 2 {
+                text.push_str(&format_part(1, &result.part1));
+            }
+            text.push_str(&format_usage(result));
+            text
+        }
+    }
+}
+
+/// Ligne des ressources consommées par un jour, vide si elles sont indisponibles
+fn format_usage(result: &DayResult) -> String {
+    result
+        .usage
+        .map(|usage| {
+            format!(
+                "  Mémoire: {} | CPU: {:.1}ms user, {:.1}ms sys\n",
+                usage.memory(),
+                usage.user_time_ms,
+                usage.system_time_ms
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Ligne du résultat d'une partie dans le détail de `run_all`
+fn format_part(part: u8, result: &PartResult) -> String {
+    match result {
+        PartResult::Answered { answer, time } => {
+            let time = time
+                .map(|time| format!(" ({})", utils::format_duration(time)))
+                .unwrap_or_default();
+            match answer {
+                Answer::Art(lines) => {
+                    let mut text = format!("  Part {}:{}\n", part, time);
+                    for line in lines {
+                        text.push_str(&format!("    {}\n", line));
+                    }
+                    text
+                }
+                answer => format!("  Part {}: {}{}\n", part, answer, time),
+            }
+        }
+        PartResult::Missing => format!("  Part {}: ❓ aucune réponse affichée\n", part),
+        PartResult::Skipped => format!("  Part {}: ⏭️  non exécutée\n", part),
+    }
+}
@@ -0,0 +1,482 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table};
+
+/// Nom du fichier de configuration à la racine du workspace
+pub const CONFIG_FILE: &str = "mush.toml";
+
+/// Préfixe des variables d'environnement surchargeant la configuration
+const ENV_PREFIX: &str = "MUSH_";
+
+/// Clés pouvant être surchargées par une variable d'environnement.
+///
+/// La variable correspondante est `MUSH_` suivi de la clé en majuscules, les
+/// points remplacés par des `_` (ex: `defaults.year` → `MUSH_DEFAULTS_YEAR`).
+const ENV_KEYS: &[&str] = &[
+    "defaults.year",
+    "defaults.release",
+    "defaults.summary_only",
+    "aoc.base_url",
+    "aoc.contact",
+    "aoc.timeout",
+    "inputs.key_file",
+    "inputs.shared_dir",
+    "scaffold.edition",
+];
+
+/// Variables d'environnement sans préfixe `MUSH_` acceptées pour certaines clés.
+///
+/// La variable `MUSH_*` de la clé reste prioritaire.
+const ENV_ALIASES: &[(&str, &str)] = &[
+    ("AOC_BASE_URL", "aoc.base_url"),
+    ("AOC_USER_AGENT_CONTACT", "aoc.contact"),
+];
+
+/// Tables remplacées entièrement par une couche plus prioritaire au lieu d'être fusionnées,
+/// pour qu'un `mush.toml` puisse retirer une dépendance par défaut.
+const REPLACED_TABLES: &[&str] = &[
+    "scaffold.dependencies",
+    "scaffold.profile",
+    "scaffold.lints",
+];
+
+/// Configuration de mush, fusionnée depuis toutes les couches
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub defaults: DefaultsConfig,
+    pub aoc: AocConfig,
+    pub inputs: InputsConfig,
+    pub scaffold: ScaffoldConfig,
+}
+
+/// Section `[defaults]` : valeurs par défaut des options de la CLI
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    /// Année utilisée quand `--year` est absent (par défaut l'année en cours)
+    pub year: Option<u16>,
+    /// Lance `run` et `run-all` en mode release
+    pub release: bool,
+    /// N'affiche que le bilan final de `run-all`
+    pub summary_only: bool,
+}
+
+impl DefaultsConfig {
+    /// Mode release d'une commande : `--release` et `--no-release` l'emportent sur `defaults.release`
+    pub fn resolve_release(&self, release: bool, no_release: bool) -> bool {
+        release || (self.release && !no_release)
+    }
+
+    /// Bilan seul pour `run-all` : `--summary-only` et `--no-summary-only` l'emportent sur `defaults.summary_only`
+    pub fn resolve_summary_only(&self, summary_only: bool, no_summary_only: bool) -> bool {
+        summary_only || (self.summary_only && !no_summary_only)
+    }
+}
+
+/// Section `[aoc]` : accès au site Advent of Code
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AocConfig {
+    /// URL de base du site (ou d'un miroir, d'un proxy, d'un faux serveur...)
+    pub base_url: String,
+    /// Contact de l'utilisateur (email, pseudo...) ajouté au User-Agent de chaque requête,
+    /// pour que les administrateurs d'Advent of Code puissent le joindre
+    pub contact: String,
+    /// Délai maximal d'une requête (`30s`, `2m`...)
+    pub timeout: String,
+}
+
+impl Default for AocConfig {
+    fn default() -> Self {
+        AocConfig {
+            base_url: "https://adventofcode.com".into(),
+            contact: String::new(),
+            timeout: "30s".into(),
+        }
+    }
+}
+
+impl AocConfig {
+    /// Délai maximal d'une requête.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si `aoc.timeout` n'est pas une durée valide.
+    pub fn timeout(&self) -> Result<std::time::Duration> {
+        crate::utils::parse_duration(&self.timeout)
+            .map_err(|e| anyhow::anyhow!("aoc.timeout : {}", e))
+    }
+}
+
+/// Section `[inputs]` : chiffrement des inputs pour les versionner
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InputsConfig {
+    /// Fichier de la clé age (par défaut `~/.config/mush/inputs.key`)
+    pub key_file: String,
+    /// Clés publiques age supplémentaires (coéquipiers, CI) pouvant déchiffrer les inputs
+    pub recipients: Vec<String>,
+    /// Répertoire partagé par défaut de `mush inputs push` et `mush inputs pull`
+    pub shared_dir: String,
+}
+
+/// Section `[scaffold]` : contenu des `Cargo.toml` générés pour chaque jour
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScaffoldConfig {
+    /// Édition Rust des packages générés
+    pub edition: String,
+    /// Table `[dependencies]` de chaque jour
+    pub dependencies: toml::Table,
+    /// Profils Cargo (`[profile.release]`, ...), appliqués au `Cargo.toml` racine
+    pub profile: toml::Table,
+    /// Table `[lints]` de chaque jour
+    pub lints: toml::Table,
+}
+
+impl Default for ScaffoldConfig {
+    fn default() -> Self {
+        let mut dependencies = toml::Table::new();
+        dependencies.insert("itertools".into(), "0.14".into());
+        dependencies.insert("regex".into(), "1.12".into());
+
+        ScaffoldConfig {
+            edition: "2021".into(),
+            dependencies,
+            profile: toml::Table::new(),
+            lints: toml::Table::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Charge la configuration en fusionnant toutes les couches.
+    ///
+    /// Par ordre de priorité croissante :
+    /// 1. Les valeurs par défaut
+    /// 2. Le fichier global `~/.config/mush/config.toml`
+    /// 3. Le fichier `mush.toml` du répertoire courant
+    /// 4. Les variables d'environnement `MUSH_*` (et `AOC_BASE_URL`, `AOC_USER_AGENT_CONTACT`)
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si un fichier existe mais n'est pas lisible ou valide.
+    pub fn load() -> Result<Config> {
+        Layers::load()?.config()
+    }
+}
+
+/// Les couches de configuration, de la moins prioritaire à la plus prioritaire
+pub struct Layers {
+    layers: Vec<(String, toml::Table)>,
+}
+
+impl Layers {
+    /// Lit toutes les couches de configuration disponibles.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si un fichier existe mais n'est pas lisible ou n'est pas un TOML valide.
+    pub fn load() -> Result<Layers> {
+        let defaults = toml::Table::try_from(Config::default())
+            .context("Impossible de sérialiser la configuration par défaut")?;
+        let mut layers = vec![("défaut".to_string(), defaults)];
+
+        if let Some(path) = global_config_path() {
+            if let Some(table) = read_table(&path)? {
+                layers.push((path.display().to_string(), table));
+            }
+        }
+        if let Some(table) = read_table(Path::new(CONFIG_FILE))? {
+            layers.push((CONFIG_FILE.to_string(), table));
+        }
+
+        let mut env_table = toml::Table::new();
+        for (var, key) in ENV_ALIASES {
+            if let Ok(raw) = std::env::var(var) {
+                set_path(&mut env_table, key, parse_value_for(key, &raw));
+            }
+        }
+        for key in ENV_KEYS {
+            let var = format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_uppercase());
+            if let Ok(raw) = std::env::var(&var) {
+                set_path(&mut env_table, key, parse_value_for(key, &raw));
+            }
+        }
+        if !env_table.is_empty() {
+            layers.push(("environnement".to_string(), env_table));
+        }
+
+        Ok(Layers { layers })
+    }
+
+    /// Configuration typée issue de la fusion des couches.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si une clé est inconnue ou a un type invalide.
+    pub fn config(&self) -> Result<Config> {
+        toml::Value::Table(self.merged())
+            .try_into()
+            .context("Configuration invalide")
+    }
+
+    /// Valeur fusionnée d'une clé pointée (ex: `defaults.year`)
+    pub fn get(&self, key: &str) -> Option<toml::Value> {
+        lookup(&self.merged(), key).cloned()
+    }
+
+    /// Toutes les clés fusionnées avec leur valeur et la couche qui les définit
+    pub fn list(&self) -> Vec<(String, toml::Value, String)> {
+        let mut entries = Vec::new();
+        flatten("", &self.merged(), &mut entries);
+
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let source = self
+                    .layers
+                    .iter()
+                    .rev()
+                    .find(|(_, table)| lookup(table, &key) == Some(&value))
+                    .map(|(source, _)| source.clone())
+                    .unwrap_or_default();
+                (key, value, source)
+            })
+            .collect()
+    }
+
+    fn merged(&self) -> toml::Table {
+        let mut merged = toml::Table::new();
+        for (_, table) in &self.layers {
+            merge(&mut merged, table);
+        }
+        merged
+    }
+}
+
+/// Modifie une clé dans un fichier de configuration en conservant sa mise en forme.
+///
+/// # Errors
+///
+/// Retourne une erreur si le fichier est invalide, si la clé n'existe pas dans
+/// la configuration ou si l'écriture échoue.
+pub fn set_value(path: &Path, key: &str, raw: &str) -> Result<()> {
+    let content = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Impossible de lire le fichier {:?}", path))?
+    } else {
+        String::new()
+    };
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Le fichier {:?} n'est pas un TOML valide", path))?;
+
+    let value = to_edit_value(parse_value_for(key, raw));
+
+    // Seule la clé écrite est validée : le reste du fichier peut encore être à corriger
+    let mut alone = DocumentMut::new();
+    insert_value(&mut alone, key, value.clone())?;
+    toml::from_str::<Config>(&alone.to_string())
+        .with_context(|| format!("Valeur invalide pour {}", key))?;
+
+    insert_value(&mut doc, key, value)?;
+    let updated = doc.to_string();
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Impossible de créer le répertoire {:?}", parent))?;
+    }
+    fs::write(path, updated)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))
+}
+
+fn insert_value(doc: &mut DocumentMut, key: &str, value: toml_edit::Value) -> Result<()> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let Some((last, parents)) = segments.split_last() else {
+        bail!("Clé vide");
+    };
+    let mut table = doc.as_table_mut() as &mut dyn toml_edit::TableLike;
+    for segment in parents {
+        table = table
+            .entry(segment)
+            .or_insert(Item::Table(Table::new()))
+            .as_table_like_mut()
+            .with_context(|| format!("La clé {} n'est pas une table", segment))?;
+    }
+    table.insert(last, toml_edit::value(value));
+    Ok(())
+}
+
+/// Chemin du fichier de configuration global (`~/.config/mush/config.toml`)
+pub fn global_config_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("mush").join("config.toml"))
+}
+
+/// Contenu par défaut de `mush.toml` généré par `mush init`
+pub fn default_config_file() -> String {
+    let scaffold = ScaffoldConfig::default();
+    let dependencies = scaffold
+        .dependencies
+        .iter()
+        .map(|(name, version)| format!("{} = {}", name, version))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"# [defaults]
+# year = 2024
+# release = true
+# summary_only = false
+
+[scaffold]
+edition = "{}"
+
+[scaffold.dependencies]
+{}
+
+# Profils appliqués au Cargo.toml racine du workspace
+# [scaffold.profile.release]
+# debug = true
+
+# Lints ajoutés au Cargo.toml de chaque jour
+# [scaffold.lints.rust]
+# unused_variables = "allow"
+"#,
+        scaffold.edition, dependencies
+    )
+}
+
+fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire le fichier {:?}", path))?;
+    let table = toml::from_str(&content)
+        .with_context(|| format!("Configuration invalide dans {:?}", path))?;
+    Ok(Some(table))
+}
+
+/// Interprète une valeur saisie comme du TOML (`2024`, `true`, ...), sinon comme une chaîne.
+///
+/// Les nombres à virgule restent des chaînes : `1.10` est une version, pas `1.1`.
+fn parse_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .filter(|value| !value.is_float())
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Interprète une valeur saisie selon le type de la clé visée.
+///
+/// `2024` est un entier pour `defaults.year` mais reste une chaîne pour `scaffold.edition`.
+fn parse_value_for(key: &str, raw: &str) -> toml::Value {
+    let value = parse_value(raw);
+    if value.is_str() || accepts(key, value.clone()) {
+        return value;
+    }
+    let text = toml::Value::String(raw.to_string());
+    if accepts(key, text.clone()) {
+        text
+    } else {
+        value
+    }
+}
+
+/// La configuration accepte-t-elle cette valeur pour cette clé ?
+fn accepts(key: &str, value: toml::Value) -> bool {
+    let mut table = toml::Table::new();
+    set_path(&mut table, key, value);
+    toml::Value::Table(table).try_into::<Config>().is_ok()
+}
+
+fn to_edit_value(value: toml::Value) -> toml_edit::Value {
+    match value {
+        toml::Value::String(s) => s.into(),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        other => other
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| other.to_string().into()),
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (head, rest) = match key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (key, None),
+    };
+    match (table.get(head)?, rest) {
+        (value, None) => Some(value),
+        (toml::Value::Table(sub), Some(rest)) => lookup(sub, rest),
+        _ => None,
+    }
+}
+
+fn set_path(table: &mut toml::Table, key: &str, value: toml::Value) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let entry = table
+                .entry(head)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(sub) = entry {
+                set_path(sub, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Fusionne récursivement `overlay` dans `base`, les valeurs d'`overlay` l'emportant.
+///
+/// Les tables de [`REPLACED_TABLES`] sont remplacées en bloc.
+fn merge(base: &mut toml::Table, overlay: &toml::Table) {
+    merge_at("", base, overlay);
+}
+
+fn merge_at(prefix: &str, base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_sub)), toml::Value::Table(overlay_sub))
+                if !REPLACED_TABLES.contains(&path.as_str()) =>
+            {
+                merge_at(&path, base_sub, overlay_sub);
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut Vec<(String, toml::Value)>) {
+    for (key, value) in table {
+        let full_key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(sub) if !sub.is_empty() => flatten(&full_key, sub, out),
+            toml::Value::Table(_) => {}
+            other => out.push((full_key, other.clone())),
+        }
+    }
+}
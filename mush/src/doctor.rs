@@ -0,0 +1,384 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command as ShellCommand;
+
+use crate::config::Config;
+use crate::examples;
+use crate::utils;
+use crate::workspace::{self, SOLUTIONS_MEMBER};
+
+/// Valeur du cookie de session écrite par `mush init`
+pub const SESSION_PLACEHOLDER: &str = "your_session_cookie_here";
+
+/// Gravité d'un problème détecté
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Empêche mush de fonctionner ou enfreint les règles d'Advent of Code
+    Error,
+    /// Gênant mais sans conséquence bloquante
+    Warning,
+}
+
+/// Correction automatique applicable avec `mush doctor --fix`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// Ajoute `solutions/*/*` aux membres du workspace racine
+    AddWorkspaceMember,
+    /// Ajoute des lignes au `.gitignore`
+    AddGitignoreLines(Vec<&'static str>),
+    /// Renomme le package d'un jour selon la convention `dayXX-YYYY`
+    RenamePackage { manifest: PathBuf, name: String },
+    /// Régénère les tests des exemples d'un jour à partir de `examples.toml`
+    SyncExamples(PathBuf),
+}
+
+/// Problème détecté dans le workspace
+#[derive(Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    pub suggestion: String,
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    fn error(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Finding {
+            severity: Severity::Error,
+            message: message.into(),
+            suggestion: suggestion.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Finding {
+            severity: Severity::Warning,
+            ..Finding::error(message, suggestion)
+        }
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Analyse le workspace du répertoire courant et retourne les problèmes détectés.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture du répertoire `solutions/` échoue.
+pub fn diagnose() -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let days = utils::scaffolded_days()?;
+
+    check_cargo(&mut findings);
+    check_root_manifest(&mut findings);
+    check_env(&mut findings);
+    check_config(&mut findings);
+    check_git(&days, &mut findings);
+    check_days(&days, &mut findings);
+
+    Ok(findings)
+}
+
+/// Applique une correction automatique.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture ou l'écriture d'un fichier échoue.
+pub fn apply(fix: &Fix) -> Result<()> {
+    match fix {
+        Fix::AddWorkspaceMember => {
+            let path = Path::new("Cargo.toml");
+            let content = read_or_empty(path)?;
+            let updated = workspace::add_workspace_member(&content, SOLUTIONS_MEMBER)?;
+            write(path, &updated)
+        }
+        Fix::AddGitignoreLines(lines) => {
+            let path = Path::new(".gitignore");
+            let content = read_or_empty(path)?;
+            write(path, &workspace::append_missing_lines(&content, lines))
+        }
+        Fix::RenamePackage { manifest, name } => {
+            let content = read_or_empty(manifest)?;
+            let mut doc = content
+                .parse::<toml_edit::DocumentMut>()
+                .with_context(|| format!("{:?} n'est pas un TOML valide", manifest))?;
+            doc["package"]["name"] = toml_edit::value(name.as_str());
+            write(manifest, &doc.to_string())
+        }
+        Fix::SyncExamples(day_path) => examples::sync(day_path).map(|_| ()),
+    }
+}
+
+fn check_cargo(findings: &mut Vec<Finding>) {
+    let available = ShellCommand::new("cargo")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !available {
+        findings.push(Finding::error(
+            "cargo est introuvable dans le PATH",
+            "Installez Rust avec rustup (https://rustup.rs) puis ouvrez un nouveau terminal",
+        ));
+    }
+}
+
+fn check_root_manifest(findings: &mut Vec<Finding>) {
+    let path = Path::new("Cargo.toml");
+    let Ok(content) = fs::read_to_string(path) else {
+        findings.push(
+            Finding::error(
+                "Aucun Cargo.toml à la racine du workspace",
+                "Lancez `mush init` depuis la racine du workspace",
+            )
+            .with_fix(Fix::AddWorkspaceMember),
+        );
+        return;
+    };
+
+    match workspace::has_workspace_member(&content, SOLUTIONS_MEMBER) {
+        Ok(true) => {}
+        Ok(false) => findings.push(
+            Finding::error(
+                format!(
+                    "Le Cargo.toml racine n'inclut pas les solutions ({:?} absent de workspace.members)",
+                    SOLUTIONS_MEMBER
+                ),
+                format!("Ajoutez {:?} à workspace.members", SOLUTIONS_MEMBER),
+            )
+            .with_fix(Fix::AddWorkspaceMember),
+        ),
+        Err(e) => findings.push(Finding::error(
+            format!("Le Cargo.toml racine est invalide : {}", e),
+            "Corrigez la syntaxe TOML du fichier",
+        )),
+    }
+}
+
+fn check_env(findings: &mut Vec<Finding>) {
+    let path = Path::new(".env");
+    if !path.exists() {
+        findings.push(Finding::error(
+            "Le fichier .env est absent",
+            format!(
+                "Lancez `mush init` ou créez .env avec AOC_SESSION={}",
+                SESSION_PLACEHOLDER
+            ),
+        ));
+        return;
+    }
+
+    let session = dotenvy::from_path_iter(path).ok().and_then(|entries| {
+        entries
+            .filter_map(Result::ok)
+            .find(|(key, _)| key == "AOC_SESSION")
+            .map(|(_, value)| value)
+    });
+    match session.as_deref().map(str::trim) {
+        None => findings.push(Finding::error(
+            "AOC_SESSION n'est pas défini dans .env",
+            "Ajoutez la ligne AOC_SESSION=<cookie de session> à .env",
+        )),
+        Some("") | Some(SESSION_PLACEHOLDER) => findings.push(Finding::error(
+            "AOC_SESSION contient encore la valeur d'exemple",
+            "Copiez le cookie `session` d'adventofcode.com dans .env (voir le README)",
+        )),
+        Some(_) => {}
+    }
+}
+
+fn check_config(findings: &mut Vec<Finding>) {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            findings.push(Finding::error(
+                format!("Configuration invalide : {:#}", err),
+                "Corrigez le fichier en cause, ou lancez `mush config set <clé> <valeur>`",
+            ));
+            return;
+        }
+    };
+    if config.aoc.contact.trim().is_empty() {
+        findings.push(Finding::warning(
+            "Aucun contact n'est défini pour le User-Agent des requêtes vers Advent of Code",
+            "Ajoutez AOC_USER_AGENT_CONTACT=<email> à .env, ou `mush config set --global aoc.contact <email>`",
+        ));
+    }
+}
+
+fn check_git(days: &[(u16, u8, PathBuf)], findings: &mut Vec<Finding>) {
+    if !git_succeeds(&["rev-parse", "--is-inside-work-tree"]) {
+        return;
+    }
+
+    if git_succeeds(&["ls-files", "--error-unmatch", ".env"]) {
+        findings.push(Finding::error(
+            ".env est suivi par git : votre cookie de session peut être publié",
+            "Lancez `git rm --cached .env` puis commitez",
+        ));
+    }
+    if !git_succeeds(&["check-ignore", "-q", "--no-index", ".env"]) {
+        findings.push(
+            Finding::error(
+                ".env n'est pas ignoré par git",
+                "Ajoutez .env au .gitignore",
+            )
+            .with_fix(Fix::AddGitignoreLines(vec![".env"])),
+        );
+    }
+
+    // Advent of Code demande de ne pas publier les inputs
+    let probe = PathBuf::from("solutions/2015/day01/input.txt");
+    let inputs: Vec<PathBuf> = days
+        .iter()
+        .map(|(_, _, path)| path.join("input.txt"))
+        .collect();
+    let not_ignored = inputs
+        .iter()
+        .chain(inputs.is_empty().then_some(&probe))
+        .any(|input| {
+            !git_succeeds(&["check-ignore", "-q", "--no-index", &input.to_string_lossy()])
+        });
+    if not_ignored {
+        findings.push(
+            Finding::error(
+                "Les fichiers input.txt ne sont pas ignorés par git",
+                "Ajoutez **/input.txt au .gitignore : Advent of Code demande de ne pas publier les inputs",
+            )
+            .with_fix(Fix::AddGitignoreLines(vec!["**/input.txt"])),
+        );
+    }
+
+    for input in &inputs {
+        if git_succeeds(&["ls-files", "--error-unmatch", &input.to_string_lossy()]) {
+            findings.push(Finding::error(
+                format!("{:?} est suivi par git", input),
+                format!("Lancez `git rm --cached {}` puis commitez", input.display()),
+            ));
+        }
+    }
+}
+
+fn check_examples(year: u16, day: u8, path: &Path, findings: &mut Vec<Finding>) {
+    let legacy = path.join(examples::LEGACY_EXAMPLE);
+    if !path.join(examples::EXAMPLES_FILE).exists() {
+        if is_empty_file(&legacy) {
+            findings.push(Finding::warning(
+                format!("{:?} est vide ou absent", legacy),
+                format!(
+                    "Ajoutez l'exemple de l'énoncé avec `mush example add -d {} -y {}`",
+                    day, year
+                ),
+            ));
+        }
+        return;
+    }
+
+    let list = match examples::load(path) {
+        Ok(list) => list,
+        Err(e) => {
+            findings.push(Finding::error(
+                format!("{:#}", e),
+                "Corrigez le fichier : une table [N] par exemple examples/N.txt",
+            ));
+            return;
+        }
+    };
+    for example in &list {
+        let file = example.path(path);
+        if is_empty_file(&file) {
+            findings.push(Finding::warning(
+                format!("{:?} est vide ou absent", file),
+                "Copiez l'exemple de l'énoncé dans ce fichier",
+            ));
+        }
+    }
+    if examples::is_outdated(path).unwrap_or(false) {
+        findings.push(
+            Finding::warning(
+                format!(
+                    "{:?} ne correspond plus à {:?}",
+                    path.join(examples::TESTS_FILE),
+                    path.join(examples::EXAMPLES_FILE)
+                ),
+                format!("Lancez `mush example sync -d {} -y {}`", day, year),
+            )
+            .with_fix(Fix::SyncExamples(path.to_path_buf())),
+        );
+    }
+}
+
+fn check_days(days: &[(u16, u8, PathBuf)], findings: &mut Vec<Finding>) {
+    for (year, day, path) in days {
+        let input = path.join("input.txt");
+        if is_empty_file(&input) {
+            findings.push(Finding::warning(
+                format!("{:?} est vide ou absent", input),
+                format!(
+                    "Relancez `mush scaffold -d {} -y {}` pour le télécharger",
+                    day, year
+                ),
+            ));
+        }
+
+        check_examples(*year, *day, path, findings);
+
+        let manifest = path.join("Cargo.toml");
+        let Ok(content) = fs::read_to_string(&manifest) else {
+            findings.push(Finding::error(
+                format!("{:?} est absent", manifest),
+                format!("Relancez `mush scaffold -d {} -y {}`", day, year),
+            ));
+            continue;
+        };
+        let expected = utils::package_name(*day, *year);
+        let actual = content
+            .parse::<toml_edit::DocumentMut>()
+            .ok()
+            .and_then(|doc| doc.get("package")?.get("name")?.as_str().map(String::from));
+        if actual.as_deref() != Some(expected.as_str()) {
+            findings.push(
+                Finding::warning(
+                    format!(
+                        "Le package de {:?} s'appelle {:?} au lieu de {:?}",
+                        path,
+                        actual.unwrap_or_default(),
+                        expected
+                    ),
+                    format!("Renommez le package en {:?} dans {:?}", expected, manifest),
+                )
+                .with_fix(Fix::RenamePackage {
+                    manifest,
+                    name: expected,
+                }),
+            );
+        }
+    }
+}
+
+fn is_empty_file(path: &Path) -> bool {
+    fs::read_to_string(path).map_or(true, |content| content.trim().is_empty())
+}
+
+fn git_succeeds(args: &[&str]) -> bool {
+    ShellCommand::new("git")
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn read_or_empty(path: &Path) -> Result<String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).with_context(|| format!("Impossible de lire le fichier {:?}", path))
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))
+}
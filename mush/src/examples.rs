@@ -0,0 +1,303 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command as ShellCommand;
+use toml_edit::DocumentMut;
+
+/// Répertoire des exemples d'un jour (`examples/1.txt`, `examples/2.txt`...)
+pub const EXAMPLES_DIR: &str = "examples";
+/// Réponses attendues de chaque exemple
+pub const EXAMPLES_FILE: &str = "examples.toml";
+/// Tests générés à partir de `examples.toml`
+pub const TESTS_FILE: &str = "src/examples.rs";
+/// Exemple unique des jours créés avant `examples/`
+pub const LEGACY_EXAMPLE: &str = "example.txt";
+
+/// Contenu de `examples.toml` à la création d'un jour
+pub const EXAMPLES_TEMPLATE: &str = r#"# Réponses attendues pour chaque exemple examples/N.txt, par exemple :
+#
+# [1]
+# part1 = "142"
+# part2 = "281"
+#
+# `mush example add` pour en ajouter, `mush example sync` après modification.
+[1]
+"#;
+
+/// En-tête du fichier de tests généré
+const TESTS_HEADER: &str = r#"// Généré par mush à partir de examples.toml : ne pas modifier.
+// Relancez `mush example sync` après avoir modifié examples.toml.
+#![allow(unused_imports, unused_macros)]
+
+use super::*;
+
+/// Vérifie la réponse d'une partie sur un fichier de examples/
+macro_rules! example {
+    ($name:ident, $part:ident, $file:literal, $expected:literal) => {
+        #[test]
+        fn $name() {
+            let input = include_str!(concat!("../examples/", $file));
+            assert_eq!($part(input).to_string(), $expected);
+        }
+    };
+}
+"#;
+
+/// Exemple d'un jour et ses réponses attendues
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub number: u32,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// Chemin du fichier de l'exemple
+    pub fn path(&self, day_path: &Path) -> PathBuf {
+        example_path(day_path, self.number)
+    }
+}
+
+/// Chemin du fichier `examples/N.txt`
+pub fn example_path(day_path: &Path, number: u32) -> PathBuf {
+    day_path.join(EXAMPLES_DIR).join(format!("{}.txt", number))
+}
+
+/// Fichier d'un exemple à passer à la solution.
+///
+/// L'exemple 1 des jours créés avant `examples/` est leur `example.txt`.
+///
+/// # Errors
+///
+/// Retourne une erreur si l'exemple n'existe pas.
+pub fn input_path(day_path: &Path, number: u32) -> Result<PathBuf> {
+    let path = example_path(day_path, number);
+    if path.exists() {
+        return Ok(path);
+    }
+    let legacy = day_path.join(LEGACY_EXAMPLE);
+    if number == 1 && legacy.exists() {
+        return Ok(legacy);
+    }
+    bail!(
+        "L'exemple {} est introuvable ({:?}) : ajoutez-le avec `mush example add`",
+        number,
+        path
+    )
+}
+
+/// Analyse `examples.toml` : une table par exemple, nommée par son numéro.
+///
+/// ```toml
+/// [1]
+/// part1 = "142"
+/// part2 = 281
+/// ```
+///
+/// # Errors
+///
+/// Retourne une erreur si le TOML est invalide ou si une clé n'est pas un numéro d'exemple.
+pub fn parse(content: &str) -> Result<Vec<Example>> {
+    let table: toml::Table = content.parse().context("TOML invalide")?;
+    let mut examples = Vec::new();
+    for (key, value) in &table {
+        let number = key
+            .parse::<u32>()
+            .ok()
+            .filter(|number| *number > 0)
+            .with_context(|| {
+                format!(
+                    "[{}] : les tables doivent porter le numéro du fichier examples/N.txt",
+                    key
+                )
+            })?;
+        let Some(answers) = value.as_table() else {
+            bail!("[{}] doit être une table (part1, part2)", key);
+        };
+        let answer = |part: &str| match answers.get(part) {
+            None => Ok(None),
+            Some(toml::Value::String(text)) => Ok(Some(text.clone())),
+            Some(toml::Value::Integer(number)) => Ok(Some(number.to_string())),
+            Some(other) => Err(anyhow::anyhow!(
+                "[{}] {} doit être un texte ou un entier, pas un {}",
+                key,
+                part,
+                other.type_str()
+            )),
+        };
+        examples.push(Example {
+            number,
+            part1: answer("part1")?,
+            part2: answer("part2")?,
+        });
+    }
+    examples.sort_by_key(|example| example.number);
+    Ok(examples)
+}
+
+/// Charge les exemples d'un jour, aucun si `examples.toml` est absent.
+///
+/// # Errors
+///
+/// Retourne une erreur si `examples.toml` est illisible ou invalide.
+pub fn load(day_path: &Path) -> Result<Vec<Example>> {
+    let path = day_path.join(EXAMPLES_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Impossible de lire le fichier {:?}", path))?;
+    parse(&content).with_context(|| format!("Le fichier {:?} est invalide", path))
+}
+
+/// Génère le module de tests : un test par exemple et par réponse attendue
+pub fn render_tests(examples: &[Example]) -> String {
+    let mut code = TESTS_HEADER.to_string();
+    if examples
+        .iter()
+        .any(|example| example.part1.is_some() || example.part2.is_some())
+    {
+        code.push('\n');
+    }
+    for example in examples {
+        for (part, expected) in [("part1", &example.part1), ("part2", &example.part2)] {
+            if let Some(expected) = expected {
+                code.push_str(&format!(
+                    "example!({part}_example_{n}, {part}, \"{n}.txt\", {expected:?});\n",
+                    part = part,
+                    n = example.number,
+                    expected = expected
+                ));
+            }
+        }
+    }
+    code
+}
+
+/// Indique si le module de tests d'un jour ne correspond plus à `examples.toml`
+pub fn is_outdated(day_path: &Path) -> Result<bool> {
+    let expected = render_tests(&load(day_path)?);
+    Ok(fs::read_to_string(day_path.join(TESTS_FILE))
+        .ok()
+        .as_deref()
+        != Some(&expected))
+}
+
+/// Régénère le module de tests d'un jour s'il a changé, et indique s'il a été réécrit.
+///
+/// # Errors
+///
+/// Retourne une erreur si `examples.toml` est invalide ou si l'écriture échoue.
+pub fn sync(day_path: &Path) -> Result<bool> {
+    if !is_outdated(day_path)? {
+        return Ok(false);
+    }
+    let path = day_path.join(TESTS_FILE);
+    fs::write(&path, render_tests(&load(day_path)?))
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    Ok(true)
+}
+
+/// Indique si le `main.rs` d'un jour inclut le module de tests généré
+pub fn is_declared(day_path: &Path) -> bool {
+    fs::read_to_string(day_path.join("src/main.rs"))
+        .is_ok_and(|content| content.contains("mod examples;"))
+}
+
+/// Ajoute un exemple et ses réponses attendues, puis régénère les tests.
+///
+/// Le premier exemple vide (celui créé par `mush scaffold`) est rempli en priorité ;
+/// sinon l'exemple prend le numéro suivant. Retourne le numéro de l'exemple.
+///
+/// # Errors
+///
+/// Retourne une erreur si `examples.toml` est invalide ou si l'écriture échoue.
+pub fn add(
+    day_path: &Path,
+    content: &str,
+    part1: Option<&str>,
+    part2: Option<&str>,
+) -> Result<u32> {
+    let examples = load(day_path)?;
+    let is_empty = |number: u32| {
+        fs::read_to_string(example_path(day_path, number))
+            .map_or(true, |content| content.trim().is_empty())
+    };
+    let number = examples
+        .iter()
+        .map(|example| example.number)
+        .find(|number| is_empty(*number))
+        .unwrap_or_else(|| next_number(day_path, &examples));
+
+    let path = example_path(day_path, number);
+    fs::create_dir_all(day_path.join(EXAMPLES_DIR))
+        .with_context(|| format!("Impossible de créer le répertoire {:?}", path.parent()))?;
+    fs::write(&path, content)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+
+    let toml_path = day_path.join(EXAMPLES_FILE);
+    let mut doc = match fs::read_to_string(&toml_path) {
+        Ok(existing) => existing
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Le fichier {:?} est invalide", toml_path))?,
+        Err(_) => DocumentMut::new(),
+    };
+    let key = number.to_string();
+    if doc.get(&key).is_none() {
+        doc[&key] = toml_edit::table();
+    }
+    for (part, expected) in [("part1", part1), ("part2", part2)] {
+        if let Some(expected) = expected {
+            doc[&key][part] = toml_edit::value(expected);
+        }
+    }
+    fs::write(&toml_path, doc.to_string())
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", toml_path))?;
+
+    sync(day_path)?;
+    Ok(number)
+}
+
+/// Numéro suivant le plus grand exemple déclaré ou présent dans `examples/`
+fn next_number(day_path: &Path, examples: &[Example]) -> u32 {
+    let files = fs::read_dir(day_path.join(EXAMPLES_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.path().file_stem()?.to_str()?.parse::<u32>().ok());
+    examples
+        .iter()
+        .map(|example| example.number)
+        .chain(files)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+/// Lit le presse-papiers avec le premier outil disponible (wl-paste, xclip, xsel, pbpaste).
+///
+/// # Errors
+///
+/// Retourne une erreur si aucun outil n'est disponible ou si le presse-papiers est vide.
+pub fn read_clipboard() -> Result<String> {
+    const TOOLS: &[(&str, &[&str])] = &[
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+        ("xsel", &["--clipboard", "--output"]),
+        ("pbpaste", &[]),
+    ];
+    for (tool, args) in TOOLS {
+        let Ok(output) = ShellCommand::new(tool).args(*args).output() else {
+            continue;
+        };
+        if output.status.success() {
+            let content = String::from_utf8(output.stdout)
+                .context("Le presse-papiers ne contient pas du texte")?;
+            if content.trim().is_empty() {
+                bail!("Le presse-papiers est vide");
+            }
+            return Ok(content);
+        }
+    }
+    bail!("Impossible de lire le presse-papiers : installez wl-clipboard, xclip ou xsel, ou passez l'exemple sur l'entrée standard")
+}
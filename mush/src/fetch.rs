@@ -1,58 +1,154 @@
 use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
 
-/// Télécharge l'input d'un challenge depuis le site adventofcode.com.
-///
-/// Utilise le cookie de session stocké dans la variable d'environnement
-/// `AOC_SESSION` pour s'authentifier auprès de l'API Advent of Code.
+use crate::config::{self, AocConfig, Config};
+
+/// Identité de mush dans le User-Agent, suivie du contact de l'utilisateur
+const USER_AGENT_PREFIX: &str = "github.com/cmoron/aoc-rustdolph";
+
+/// Fichier témoin, à côté de la configuration globale, indiquant que l'avertissement
+/// sur le contact absent a déjà été affiché
+const CONTACT_WARNING_MARKER: &str = "contact-warning-shown";
+
+/// Client HTTP du site Advent of Code.
 ///
-/// # Arguments
+/// L'URL de base, le contact du User-Agent et le délai maximal des requêtes
+/// viennent de la section `[aoc]` de la configuration, et s'appliquent à
+/// chaque requête : on peut ainsi viser un miroir, un proxy ou un faux serveur.
+pub struct Client {
+    http: reqwest::blocking::Client,
+    base_url: String,
+}
+
+impl Client {
+    /// Client configuré par la section `[aoc]` de la configuration.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si `aoc.timeout` est invalide ou si le client HTTP ne
+    /// peut pas être créé.
+    pub fn new(aoc: &AocConfig) -> Result<Client> {
+        if aoc.contact.trim().is_empty() {
+            warn_missing_contact();
+        }
+
+        let http = reqwest::blocking::Client::builder()
+            .user_agent(user_agent(&aoc.contact))
+            .timeout(aoc.timeout()?)
+            .build()
+            .context("Impossible de créer le client HTTP")?;
+
+        Ok(Client {
+            http,
+            base_url: aoc.base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Client configuré par la configuration chargée depuis toutes les couches.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la configuration est invalide.
+    pub fn from_config() -> Result<Client> {
+        Client::new(&Config::load()?.aoc)
+    }
+
+    /// Télécharge l'input d'un challenge.
+    ///
+    /// Utilise le cookie de session stocké dans la variable d'environnement
+    /// `AOC_SESSION` pour s'authentifier auprès de l'API Advent of Code.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - Le jour du challenge (1-25)
+    /// * `year` - L'année du challenge
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si :
+    /// - La variable d'environnement `AOC_SESSION` n'est pas définie
+    /// - La requête HTTP échoue ou dépasse le délai maximal
+    /// - Le serveur retourne une erreur (status non-200)
+    /// - La lecture de la réponse échoue
+    pub fn fetch_input(&self, day: u8, year: u16) -> Result<String> {
+        let session = std::env::var("AOC_SESSION")
+            .context("La variable d'environnement AOC_SESSION n'est pas définie dans .env")?;
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        let response = self
+            .http
+            .get(&url)
+            .header("Cookie", format!("session={}", session))
+            .send()
+            .with_context(|| format!("Erreur lors de la requête vers {}", url))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Erreur lors de la récupération de l'input: statut {}",
+                response.status()
+            ));
+        }
+
+        let text = response
+            .text()
+            .with_context(|| "Erreur lors de la lecture de la réponse")?;
+
+        Ok(text)
+    }
+}
+
+/// Télécharge l'input d'un challenge avec le client de la configuration.
 ///
-/// * `day` - Le jour du challenge (1-25)
-/// * `year` - L'année du challenge
+/// Voir [`Client::fetch_input`].
 ///
 /// # Errors
 ///
-/// Retourne une erreur si :
-/// - La variable d'environnement `AOC_SESSION` n'est pas définie
-/// - La requête HTTP échoue
-/// - Le serveur retourne une erreur (status non-200)
-/// - La lecture de la réponse échoue
+/// Retourne une erreur si la configuration est invalide ou si le téléchargement échoue.
 ///
 /// # Notes
 ///
 /// Inclut un User-Agent conformément aux recommandations de l'API AOC.
 pub fn fetch_input(day: u8, year: u16) -> Result<String> {
-    fetch_input_with_base_url(day, year, "https://adventofcode.com")
+    Client::from_config()?.fetch_input(day, year)
 }
 
-/// Version interne de fetch_input permettant de spécifier l'URL de base (pour les tests).
-pub fn fetch_input_with_base_url(day: u8, year: u16, base_url: &str) -> Result<String> {
-    let session = std::env::var("AOC_SESSION")
-        .context("La variable d'environnement AOC_SESSION n'est pas définie dans .env")?;
-
-    let url = format!("{}/{}/day/{}/input", base_url, year, day);
-
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(&url)
-        .header("Cookie", format!("session={}", session))
-        .header(
-            "User-Agent",
-            "github.com/cmoron/aoc-rustdolph by cyril.moron@gmail.com",
-        )
-        .send()
-        .with_context(|| format!("Erreur lors de la requête vers {}", url))?;
-
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "Erreur lors de la récupération de l'input: statut {}",
-            response.status()
-        ));
+/// Avertit qu'aucun contact n'est configuré, si l'avertissement n'a encore jamais été affiché.
+///
+/// Un fichier témoin est écrit à côté de la configuration globale pour ne pas le
+/// répéter aux commandes suivantes ; `mush doctor` continue de signaler l'absence
+/// de contact. Retourne `true` si l'avertissement a été affiché.
+pub fn warn_missing_contact() -> bool {
+    let marker = contact_warning_marker();
+    if marker.as_ref().is_some_and(|marker| marker.exists()) {
+        return false;
     }
 
-    let text = response
-        .text()
-        .with_context(|| "Erreur lors de la lecture de la réponse")?;
+    println!("⚠️  Aucun contact dans le User-Agent : les administrateurs d'Advent of Code ne pourront pas vous joindre au sujet de vos requêtes.");
+    println!("   Ajoutez AOC_USER_AGENT_CONTACT=<email> à .env, ou lancez `mush config set --global aoc.contact <email>`.");
+    println!("   Cet avertissement ne sera plus affiché (voir `mush doctor`).");
 
-    Ok(text)
+    // Sans répertoire de configuration accessible, l'avertissement reviendra à la prochaine commande
+    if let Some(marker) = marker {
+        if let Some(parent) = marker.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(&marker, "");
+    }
+    true
+}
+
+/// Chemin du fichier témoin de l'avertissement sur le contact absent
+pub fn contact_warning_marker() -> Option<PathBuf> {
+    config::global_config_path()
+        .and_then(|path| path.parent().map(|dir| dir.join(CONTACT_WARNING_MARKER)))
+}
+
+/// User-Agent envoyé au site : identité de mush, suivie du contact de l'utilisateur s'il est défini
+pub fn user_agent(contact: &str) -> String {
+    match contact.trim() {
+        "" => USER_AGENT_PREFIX.to_string(),
+        contact => format!("{} by {}", USER_AGENT_PREFIX, contact),
+    }
 }
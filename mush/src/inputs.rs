@@ -0,0 +1,381 @@
+use age::secrecy::ExposeSecret;
+use age::x25519::{Identity, Recipient};
+use anyhow::{anyhow, bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, InputsConfig};
+use crate::utils;
+
+/// Input en clair d'un jour, ignoré par git
+pub const INPUT_FILE: &str = "input.txt";
+/// Input chiffré d'un jour, pouvant être commité
+pub const ENCRYPTED_INPUT: &str = "input.txt.age";
+/// Variable d'environnement contenant la clé age (`AGE-SECRET-KEY-1...`), pour la CI
+pub const KEY_ENV: &str = "AOC_INPUTS_KEY";
+/// Somme de contrôle d'un input du répertoire partagé, au format de `sha256sum`
+pub const CHECKSUM_FILE: &str = "input.txt.sha256";
+
+/// Résultat du chiffrement ou du déchiffrement de l'input d'un jour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Le fichier de destination a été écrit
+    Written,
+    /// Le fichier de destination avait déjà le bon contenu
+    Unchanged,
+    /// Pas de fichier source (ou un fichier vide)
+    Missing,
+    /// Le fichier de destination a un autre contenu et n'a pas été écrasé
+    Conflict,
+    /// L'input partagé ne correspond pas à sa somme de contrôle
+    Corrupted,
+}
+
+/// Fichier de la clé : `inputs.key_file` (`~/` accepté), sinon `inputs.key` à côté de la
+/// configuration globale
+pub fn key_path(inputs: &InputsConfig) -> Option<PathBuf> {
+    if !inputs.key_file.is_empty() {
+        return expand_home(&inputs.key_file);
+    }
+    config::global_config_path().and_then(|path| Some(path.parent()?.join("inputs.key")))
+}
+
+/// Répertoire partagé : celui donné en argument, sinon `inputs.shared_dir`.
+///
+/// # Errors
+///
+/// Retourne une erreur si aucun répertoire n'est donné ni configuré.
+pub fn shared_dir(dir: Option<&Path>, inputs: &InputsConfig) -> Result<PathBuf> {
+    if let Some(dir) = dir {
+        return Ok(dir.to_path_buf());
+    }
+    if inputs.shared_dir.is_empty() {
+        bail!("Aucun répertoire partagé : passez-le en argument ou configurez inputs.shared_dir");
+    }
+    expand_home(&inputs.shared_dir).context("Impossible de résoudre inputs.shared_dir")
+}
+
+/// Chemin avec un éventuel `~/` remplacé par le répertoire de l'utilisateur
+fn expand_home(path: &str) -> Option<PathBuf> {
+    match path.strip_prefix("~/") {
+        Some(relative) => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(relative)),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+/// Génère une clé age et l'écrit dans un fichier lisible seulement par l'utilisateur.
+///
+/// Retourne la clé publique correspondante.
+///
+/// # Errors
+///
+/// Retourne une erreur si le fichier existe déjà ou ne peut pas être écrit.
+pub fn generate_key(path: &Path) -> Result<Recipient> {
+    if path.exists() {
+        bail!(
+            "La clé {:?} existe déjà : supprimez-la d'abord si vous voulez la remplacer",
+            path
+        );
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Impossible de créer le répertoire {:?}", parent))?;
+    }
+
+    let identity = Identity::generate();
+    let recipient = identity.to_public();
+    let content = format!(
+        "# created: {}\n# public key: {}\n{}\n",
+        chrono::Local::now().to_rfc3339(),
+        recipient,
+        identity.to_string().expose_secret()
+    );
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .with_context(|| format!("Impossible d'écrire la clé dans {:?}", path))?;
+
+    Ok(recipient)
+}
+
+/// Lit une clé age (`AGE-SECRET-KEY-1...`), en ignorant les commentaires de `age-keygen`.
+///
+/// # Errors
+///
+/// Retourne une erreur si aucune clé valide n'est trouvée.
+pub fn parse_identity(content: &str) -> Result<Identity> {
+    let key = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .context("Aucune clé age trouvée")?;
+    key.parse::<Identity>()
+        .map_err(|e| anyhow!("Clé age invalide : {}", e))
+}
+
+/// Charge la clé depuis `AOC_INPUTS_KEY`, sinon depuis son fichier.
+///
+/// # Errors
+///
+/// Retourne une erreur si aucune clé n'est disponible ou si elle est invalide.
+pub fn load_identity(inputs: &InputsConfig) -> Result<Identity> {
+    if let Ok(key) = std::env::var(KEY_ENV) {
+        return parse_identity(&key).with_context(|| format!("{} est invalide", KEY_ENV));
+    }
+    let path = key_path(inputs).context("Impossible de déterminer le fichier de la clé")?;
+    let content = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "Clé introuvable : définissez {} ou créez {:?} avec `mush inputs keygen`",
+            KEY_ENV, path
+        )
+    })?;
+    parse_identity(&content).with_context(|| format!("Le fichier {:?} est invalide", path))
+}
+
+/// Destinataires du chiffrement : la clé publique de l'utilisateur et `inputs.recipients`.
+///
+/// # Errors
+///
+/// Retourne une erreur si une clé publique de la configuration est invalide.
+pub fn recipients(identity: &Identity, inputs: &InputsConfig) -> Result<Vec<Recipient>> {
+    let mut recipients = vec![identity.to_public()];
+    for raw in &inputs.recipients {
+        let recipient = raw.trim().parse::<Recipient>().map_err(|e| {
+            anyhow!(
+                "Clé publique invalide dans inputs.recipients ({}) : {}",
+                raw,
+                e
+            )
+        })?;
+        recipients.push(recipient);
+    }
+    Ok(recipients)
+}
+
+/// Chiffre un texte pour des destinataires, au format age ASCII (`-----BEGIN AGE ENCRYPTED FILE-----`).
+///
+/// # Errors
+///
+/// Retourne une erreur si le chiffrement échoue.
+pub fn encrypt(plaintext: &str, recipients: &[Recipient]) -> Result<String> {
+    let encryptor = age::Encryptor::with_recipients(
+        recipients
+            .iter()
+            .map(|recipient| recipient as &dyn age::Recipient),
+    )
+    .context("Aucun destinataire pour le chiffrement")?;
+
+    let mut ciphertext = Vec::new();
+    let armored =
+        age::armor::ArmoredWriter::wrap_output(&mut ciphertext, age::armor::Format::AsciiArmor)?;
+    let mut writer = encryptor.wrap_output(armored)?;
+    writer.write_all(plaintext.as_bytes())?;
+    writer.finish()?.finish()?;
+
+    String::from_utf8(ciphertext).context("Chiffrement invalide")
+}
+
+/// Déchiffre un texte chiffré par [`encrypt`].
+///
+/// # Errors
+///
+/// Retourne une erreur si le texte n'est pas chiffré pour cette clé.
+pub fn decrypt(ciphertext: &str, identity: &Identity) -> Result<String> {
+    let plaintext = age::decrypt(identity, ciphertext.as_bytes())
+        .map_err(|e| anyhow!("Déchiffrement impossible : {}", e))?;
+    String::from_utf8(plaintext).context("L'input déchiffré n'est pas du texte")
+}
+
+/// Chiffre le `input.txt` d'un jour dans `input.txt.age`.
+///
+/// Un `input.txt.age` qui se déchiffre déjà en `input.txt` n'est pas réécrit, pour ne
+/// pas modifier le fichier versionné (le chiffrement change à chaque fois), sauf avec
+/// `force` pour l'ouvrir à de nouveaux destinataires. Un `input.txt.age` illisible
+/// avec cette clé n'est remplacé qu'avec `force`.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture, le chiffrement ou l'écriture échoue.
+pub fn encrypt_day(
+    day_path: &Path,
+    identity: &Identity,
+    recipients: &[Recipient],
+    force: bool,
+) -> Result<Outcome> {
+    let Some(plaintext) = read_non_empty(&day_path.join(INPUT_FILE))? else {
+        return Ok(Outcome::Missing);
+    };
+
+    let path = day_path.join(ENCRYPTED_INPUT);
+    if let Some(existing) = read_non_empty(&path)? {
+        match decrypt(&existing, identity) {
+            Ok(decrypted) if decrypted == plaintext && !force => return Ok(Outcome::Unchanged),
+            Ok(_) => {}
+            Err(_) if !force => return Ok(Outcome::Conflict),
+            Err(_) => {}
+        }
+    }
+
+    fs::write(&path, encrypt(&plaintext, recipients)?)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    Ok(Outcome::Written)
+}
+
+/// Déchiffre le `input.txt.age` d'un jour dans `input.txt`.
+///
+/// Un `input.txt` différent de l'input déchiffré n'est remplacé qu'avec `force`.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture, le déchiffrement ou l'écriture échoue.
+pub fn decrypt_day(day_path: &Path, identity: &Identity, force: bool) -> Result<Outcome> {
+    let encrypted_path = day_path.join(ENCRYPTED_INPUT);
+    let Some(ciphertext) = read_non_empty(&encrypted_path)? else {
+        return Ok(Outcome::Missing);
+    };
+    let plaintext = decrypt(&ciphertext, identity)
+        .with_context(|| format!("Impossible de déchiffrer {:?}", encrypted_path))?;
+
+    let path = day_path.join(INPUT_FILE);
+    match read_non_empty(&path)? {
+        Some(existing) if existing == plaintext => return Ok(Outcome::Unchanged),
+        Some(_) if !force => return Ok(Outcome::Conflict),
+        _ => {}
+    }
+
+    fs::write(&path, plaintext)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    Ok(Outcome::Written)
+}
+
+/// Déchiffre l'input d'un jour si `input.txt` est absent ou vide et que `input.txt.age` existe.
+///
+/// Retourne `true` si `input.txt` a été écrit.
+///
+/// # Errors
+///
+/// Retourne une erreur si la clé est introuvable ou si le déchiffrement échoue.
+pub fn ensure_plaintext(day_path: &Path) -> Result<bool> {
+    if read_non_empty(&day_path.join(INPUT_FILE))?.is_some()
+        || !day_path.join(ENCRYPTED_INPUT).exists()
+    {
+        return Ok(false);
+    }
+    let identity = load_identity(&config::Config::load()?.inputs)?;
+    Ok(decrypt_day(day_path, &identity, false)? == Outcome::Written)
+}
+
+/// Répertoire d'un jour dans le répertoire partagé (`<dir>/2024/day01`)
+pub fn shared_day_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{:02}", day))
+}
+
+/// Jours ayant un input dans le répertoire partagé, triés par année puis par jour
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture du répertoire partagé échoue.
+pub fn shared_days(dir: &Path) -> Result<Vec<(u16, u8)>> {
+    Ok(utils::day_dirs(dir)?
+        .into_iter()
+        .filter(|(_, _, path)| path.join(INPUT_FILE).is_file())
+        .map(|(year, day, _)| (year, day))
+        .collect())
+}
+
+/// Somme SHA-256 d'un contenu, en hexadécimal
+pub fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Copie le `input.txt` d'un jour dans le répertoire partagé, avec sa somme de contrôle.
+///
+/// Un input partagé différent (ou corrompu) n'est remplacé qu'avec `force`.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture ou l'écriture échoue.
+pub fn push_day(day_path: &Path, shared_day: &Path, force: bool) -> Result<Outcome> {
+    let Some(content) = read_non_empty(&day_path.join(INPUT_FILE))? else {
+        return Ok(Outcome::Missing);
+    };
+    match read_shared(shared_day)? {
+        Some((shared, true)) if shared == content => return Ok(Outcome::Unchanged),
+        Some((_, true)) if !force => return Ok(Outcome::Conflict),
+        Some((_, false)) if !force => return Ok(Outcome::Corrupted),
+        _ => {}
+    }
+
+    fs::create_dir_all(shared_day)
+        .with_context(|| format!("Impossible de créer le répertoire {:?}", shared_day))?;
+    let path = shared_day.join(INPUT_FILE);
+    fs::write(&path, &content)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    let checksum = shared_day.join(CHECKSUM_FILE);
+    fs::write(
+        &checksum,
+        format!("{}  {}\n", sha256_hex(content.as_bytes()), INPUT_FILE),
+    )
+    .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", checksum))?;
+    Ok(Outcome::Written)
+}
+
+/// Copie l'input partagé d'un jour dans son `input.txt`, après vérification de sa somme.
+///
+/// Un `input.txt` local différent n'est remplacé qu'avec `force` ; un input partagé
+/// corrompu n'est jamais copié.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture ou l'écriture échoue.
+pub fn pull_day(shared_day: &Path, day_path: &Path, force: bool) -> Result<Outcome> {
+    let content = match read_shared(shared_day)? {
+        None => return Ok(Outcome::Missing),
+        Some((_, false)) => return Ok(Outcome::Corrupted),
+        Some((content, true)) => content,
+    };
+
+    let path = day_path.join(INPUT_FILE);
+    match read_non_empty(&path)? {
+        Some(local) if local == content => return Ok(Outcome::Unchanged),
+        Some(_) if !force => return Ok(Outcome::Conflict),
+        _ => {}
+    }
+
+    fs::write(&path, content)
+        .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))?;
+    Ok(Outcome::Written)
+}
+
+/// Input partagé d'un jour et validité de sa somme de contrôle (absente = invalide)
+fn read_shared(shared_day: &Path) -> Result<Option<(String, bool)>> {
+    let Some(content) = read_non_empty(&shared_day.join(INPUT_FILE))? else {
+        return Ok(None);
+    };
+    let expected = read_non_empty(&shared_day.join(CHECKSUM_FILE))?;
+    let valid = expected
+        .as_deref()
+        .and_then(|line| line.split_whitespace().next())
+        .is_some_and(|hash| hash.eq_ignore_ascii_case(&sha256_hex(content.as_bytes())));
+    Ok(Some((content, valid)))
+}
+
+/// Contenu d'un fichier, `None` s'il est absent ou vide
+fn read_non_empty(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire le fichier {:?}", path))?;
+    Ok((!content.is_empty()).then_some(content))
+}
@@ -0,0 +1,68 @@
+/// Résultat des tests d'un package, lu dans la sortie de libtest
+#[derive(Debug, Default, PartialEq)]
+pub struct TestReport {
+    pub passed: usize,
+    pub failed: usize,
+    /// Tests marqués `#[ignore]`
+    pub ignored: usize,
+    /// Tests en échec et leur sortie (message d'assertion...)
+    pub failures: Vec<(String, String)>,
+}
+
+impl TestReport {
+    /// Analyse la sortie standard de libtest (format `pretty` par défaut).
+    ///
+    /// Retourne `None` si aucun test n'a été lancé (erreur de compilation...).
+    pub fn parse(stdout: &str) -> Option<TestReport> {
+        let mut report = TestReport::default();
+        let mut ran = false;
+        let mut current: Option<(String, Vec<&str>)> = None;
+
+        for line in stdout.lines() {
+            if line.starts_with("running ") {
+                ran = true;
+            }
+            if let Some(rest) = line.strip_prefix("test ") {
+                if rest.ends_with(" ... ok") {
+                    report.passed += 1;
+                } else if rest.ends_with(" ... FAILED") {
+                    report.failed += 1;
+                } else if rest.contains(" ... ignored") {
+                    // "... ignored" ou "... ignored, raison"
+                    report.ignored += 1;
+                }
+            }
+
+            // Sections "---- nom stdout ----" qui suivent "failures:"
+            if let Some(name) = line
+                .strip_prefix("---- ")
+                .and_then(|rest| rest.strip_suffix(" stdout ----"))
+            {
+                report.push_failure(current.take());
+                current = Some((name.to_string(), Vec::new()));
+            } else if line == "failures:" || line.starts_with("test result:") {
+                report.push_failure(current.take());
+            } else if let Some((_, output)) = current.as_mut() {
+                output.push(line);
+            }
+        }
+
+        ran.then_some(report)
+    }
+
+    /// Nombre de tests exécutés (hors ignorés)
+    pub fn total(&self) -> usize {
+        self.passed + self.failed
+    }
+
+    fn push_failure(&mut self, failure: Option<(String, Vec<&str>)>) {
+        if let Some((name, output)) = failure {
+            let output: Vec<&str> = output
+                .into_iter()
+                .take_while(|line| !line.starts_with("stack backtrace:"))
+                .filter(|line| !line.trim().is_empty() && !line.starts_with("note:"))
+                .collect();
+            self.failures.push((name, output.join("\n")));
+        }
+    }
+}
@@ -4,10 +4,24 @@
 //! Automatise la création de la structure de projet, le téléchargement des inputs
 //! et l'exécution des solutions.
 
+mod build;
+mod calendar;
+mod chart;
 mod commands;
+mod config;
+mod doctor;
+mod examples;
 mod fetch;
+mod inputs;
+mod libtest;
+mod manifest;
+mod readme;
 mod results;
+mod runner;
+mod tui;
 mod utils;
+mod watch;
+mod workspace;
 
 use anyhow::Result;
 use chrono::Datelike;
@@ -24,8 +38,12 @@ struct Cli {
 /// Commandes disponibles dans la CLI
 #[derive(Subcommand)]
 enum Commands {
-    /// Initialise le workspace avec les fichiers de configuration nécessaires
-    Init,
+    /// Initialise le workspace, ou complète les fichiers de configuration existants
+    Init {
+        /// Applique les modifications des fichiers existants sans demander de confirmation
+        #[arg(short = 'y', long, default_value_t = false)]
+        yes: bool,
+    },
 
     /// Génère la structure de projet pour un jour spécifique et télécharge l'input
     Scaffold {
@@ -33,7 +51,7 @@ enum Commands {
         #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
         day: u8,
 
-        /// L'année (ex: 2024). Optionnel, par défaut l'année en cours.
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
         #[arg(short, long)]
         year: Option<u16>,
     },
@@ -44,28 +62,350 @@ enum Commands {
         #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
         day: u8,
 
-        /// L'année (ex: 2024). Optionnel, par défaut l'année en cours.
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
         #[arg(short, long)]
         year: Option<u16>,
 
-        /// Lance en mode release (optimisé)
-        #[arg(short, long, default_value_t = false)]
+        /// Lance en mode release (optimisé), activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
+        release: bool,
+
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
+
+        /// N'exécute que cette partie (1 ou 2)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Fichier d'input à utiliser à la place de input.txt
+        #[arg(short, long, conflicts_with = "example")]
+        input: Option<std::path::PathBuf>,
+
+        /// Utilise l'exemple `examples/N.txt` comme input (1 par défaut)
+        #[arg(short, long, num_args = 0..=1, default_missing_value = "1", value_name = "N")]
+        example: Option<u32>,
+    },
+
+    /// Relance les tests et la solution d'un jour à chaque modification
+    Watch {
+        /// Le jour du challenge (1-25)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Lance en mode release (optimisé), activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
+        release: bool,
+
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
+    },
+
+    /// Gère les exemples d'un jour (`examples/N.txt` et leurs réponses dans `examples.toml`)
+    Example {
+        #[command(subcommand)]
+        command: ExampleCommands,
+    },
+
+    /// Lance les tests d'un jour, ou de tous les jours d'une année, et affiche un résumé
+    Test {
+        /// Le jour du challenge (1-25). Par défaut, tous les jours créés de l'année.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Compile les tests en mode release, activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
         release: bool,
+
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
     },
 
     /// Lance l'exécution de tous les jours d'une année et affiche un bilan
     RunAll {
-        /// L'année (ex: 2024). Optionnel, par défaut l'année en cours.
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
         #[arg(short, long)]
         year: Option<u16>,
 
-        /// Lance en mode release (optimisé)
+        /// Lance en mode release (optimisé), activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
+        release: bool,
+
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
+
+        /// Affiche uniquement le bilan final, activé par défaut si `defaults.summary_only`
+        #[arg(
+            short,
+            long,
+            default_value_t = false,
+            overrides_with = "no_summary_only"
+        )]
+        summary_only: bool,
+
+        /// Affiche le détail de chaque jour même si `defaults.summary_only` est activé
+        #[arg(long, default_value_t = false, overrides_with = "summary_only")]
+        no_summary_only: bool,
+
+        /// N'exécute que cette partie (1 ou 2)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Délai maximal par partie (ex: 30s, 500ms, 2m) avant d'interrompre le jour
+        #[arg(short, long, value_parser = utils::parse_duration)]
+        timeout: Option<std::time::Duration>,
+
+        /// Nombre de jours exécutés en parallèle (1 par défaut, pour des temps fiables)
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// Affiche les résultats en JSON (temps, mémoire, CPU) au lieu du bilan
+        #[arg(long, default_value_t = false)]
+        json: bool,
+
+        /// Affiche la sortie d'erreur complète des jours en échec
         #[arg(short, long, default_value_t = false)]
+        verbose: bool,
+    },
+
+    /// Met à jour le tableau de progression du README (entre `<!-- mush:start -->` et `<!-- mush:end -->`)
+    Readme {
+        /// Limite le tableau à cette année. Par défaut, toutes les années ayant des jours créés.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Exécute les jours avant de générer le tableau, au lieu d'utiliser les derniers résultats
+        #[arg(long, default_value_t = false)]
+        run: bool,
+
+        /// Exécute les jours en mode release (avec --run), activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
+        release: bool,
+
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
+
+        /// Fichier à mettre à jour
+        #[arg(short, long, default_value = "README.md")]
+        file: std::path::PathBuf,
+    },
+
+    /// Génère un histogramme SVG des temps par jour (échelle logarithmique)
+    Chart {
+        /// Année à afficher, répétable pour superposer plusieurs années. Par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Vec<u16>,
+
+        /// Fichier de résultats enregistré (`results-YYYY.json`, `run-all --json`) à superposer, répétable
+        #[arg(long)]
+        from: Vec<std::path::PathBuf>,
+
+        /// Fichier SVG à écrire
+        #[arg(short, long, default_value = "chart.svg")]
+        output: std::path::PathBuf,
+    },
+
+    /// Ouvre un tableau de bord interactif des jours d'une année
+    Tui {
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Exécute les jours en mode release, activé par défaut si `defaults.release`
+        #[arg(short, long, default_value_t = false, overrides_with = "no_release")]
         release: bool,
 
-        /// Affiche uniquement le bilan final
+        /// Désactive le mode release même si `defaults.release` est activé
+        #[arg(long, default_value_t = false, overrides_with = "release")]
+        no_release: bool,
+    },
+
+    /// Diagnostique le workspace et suggère des corrections
+    Doctor {
+        /// Applique les corrections sans risque
+        #[arg(long, default_value_t = false)]
+        fix: bool,
+    },
+
+    /// Lit ou modifie la configuration (`mush.toml`, `~/.config/mush/config.toml`)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Gère les dépendances des jours à partir de `mush.toml`
+    Deps {
+        #[command(subcommand)]
+        command: DepsCommands,
+    },
+
+    /// Chiffre les inputs pour les commiter, ou les partage via un répertoire commun
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommands,
+    },
+}
+
+/// Sous-commandes de `mush config`
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Affiche la valeur effective d'une clé (ex: defaults.year)
+    Get {
+        /// La clé, sections séparées par des points
+        key: String,
+    },
+
+    /// Modifie une clé dans `mush.toml` (ou dans la configuration globale)
+    Set {
+        /// La clé, sections séparées par des points
+        key: String,
+
+        /// La valeur, interprétée comme du TOML si possible (2024, true, ...)
+        value: String,
+
+        /// Écrit dans `~/.config/mush/config.toml` plutôt que dans `mush.toml`
         #[arg(short, long, default_value_t = false)]
-        summary_only: bool,
+        global: bool,
+    },
+
+    /// Liste toutes les clés effectives et leur provenance
+    List,
+}
+
+/// Sous-commandes de `mush example`
+#[derive(Subcommand)]
+enum ExampleCommands {
+    /// Ajoute un exemple lu sur l'entrée standard, ou dans le presse-papiers
+    Add {
+        /// Le jour du challenge (1-25)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Réponse attendue de la partie 1 pour cet exemple
+        #[arg(long)]
+        part1: Option<String>,
+
+        /// Réponse attendue de la partie 2 pour cet exemple
+        #[arg(long)]
+        part2: Option<String>,
+
+        /// Lit l'exemple dans le presse-papiers plutôt que sur l'entrée standard
+        #[arg(short, long, default_value_t = false)]
+        clipboard: bool,
+    },
+
+    /// Régénère les tests des exemples après une modification de `examples.toml`
+    Sync {
+        /// Le jour du challenge (1-25)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// L'année (ex: 2024). Optionnel, par défaut `defaults.year` ou l'année en cours.
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+}
+
+/// Sous-commandes de `mush deps`
+#[derive(Subcommand)]
+enum DepsCommands {
+    /// Aligne les Cargo.toml des jours existants sur la configuration
+    Sync,
+}
+
+/// Sous-commandes de `mush inputs`
+#[derive(Subcommand)]
+enum InputsCommands {
+    /// Génère la clé age utilisée pour chiffrer les inputs
+    Keygen {
+        /// Fichier de la clé. Par défaut `inputs.key_file` ou `~/.config/mush/inputs.key`.
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Chiffre le `input.txt` de chaque jour dans `input.txt.age`
+    Encrypt {
+        /// Le jour du challenge (1-25). Par défaut, tous les jours.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// L'année (ex: 2024). Par défaut, toutes les années.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Rechiffre tous les inputs (après l'ajout d'un destinataire), y compris ceux
+        /// chiffrés avec une autre clé
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+
+    /// Déchiffre le `input.txt.age` de chaque jour dans `input.txt`
+    Decrypt {
+        /// Le jour du challenge (1-25). Par défaut, tous les jours.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// L'année (ex: 2024). Par défaut, toutes les années.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Remplace les `input.txt` différents de l'input déchiffré
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+
+    /// Copie les `input.txt` vers un répertoire partagé (`<dir>/2024/day01/input.txt`)
+    Push {
+        /// Le répertoire partagé. Par défaut `inputs.shared_dir`.
+        #[arg(long)]
+        to: Option<std::path::PathBuf>,
+
+        /// Le jour du challenge (1-25). Par défaut, tous les jours.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// L'année (ex: 2024). Par défaut, toutes les années.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Remplace les inputs partagés différents ou corrompus
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+
+    /// Copie les inputs d'un répertoire partagé dans le `input.txt` de chaque jour
+    Pull {
+        /// Le répertoire partagé. Par défaut `inputs.shared_dir`.
+        #[arg(long, alias = "to")]
+        from: Option<std::path::PathBuf>,
+
+        /// Le jour du challenge (1-25). Par défaut, tous les jours.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// L'année (ex: 2024). Par défaut, toutes les années.
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Remplace les `input.txt` différents de l'input partagé
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
     },
 }
 
@@ -73,65 +413,274 @@ fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
     let cli = Cli::parse();
+    // `init`, `config` et `doctor` restent utilisables pour corriger une configuration invalide
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(err)
+            if matches!(
+                cli.command,
+                Commands::Init { .. } | Commands::Config { .. } | Commands::Doctor { .. }
+            ) =>
+        {
+            eprintln!("⚠️  {:#}", err);
+            config::Config::default()
+        }
+        Err(err) => return Err(err),
+    };
+    let default_year = config
+        .defaults
+        .year
+        .unwrap_or_else(|| chrono::Utc::now().year() as u16);
 
     match &cli.command {
-        Commands::Init => {
+        Commands::Init { yes } => {
             println!("🎄 Initialisation du Workspace aoc-rustdolph...");
-            commands::initialize_workspace()?;
+            commands::initialize_workspace(*yes)?;
         }
         Commands::Scaffold { day, year } => {
-            let current_year = chrono::Utc::now().year() as u16;
-            let year = year.unwrap_or(current_year);
+            let year = year.unwrap_or(default_year);
+
+            resolve_event(year)?;
 
             println!("🎄 Préparation du jour {} de l'année {}...", day, year);
             commands::create_scaffold(*day, year)?;
         }
-        Commands::Run { day, year, release } => {
-            let current_year = chrono::Utc::now().year() as u16;
-            let year = year.unwrap_or(current_year);
-
-            let package_name = format!("day{:02}-{}", day, year);
-            println!(
-                "🚀 Lancement du jour {} de l'année {} (package: {})...",
-                day, year, package_name
-            );
+        Commands::Run {
+            day,
+            year,
+            release,
+            no_release,
+            part,
+            input,
+            example,
+        } => {
+            let year = year.unwrap_or(default_year);
+            let release = config.defaults.resolve_release(*release, *no_release);
+            resolve_event(year)?.check_day(*day)?;
+
+            let input = match example {
+                Some(number) => Some(examples::input_path(&utils::day_path(*day, year), *number)?),
+                None => input.clone(),
+            };
+            commands::run_day(*day, year, release, *part, input.as_deref())?;
+        }
+        Commands::Watch {
+            day,
+            year,
+            release,
+            no_release,
+        } => {
+            let year = year.unwrap_or(default_year);
+            let release = config.defaults.resolve_release(*release, *no_release);
+            resolve_event(year)?.check_day(*day)?;
 
-            let mut command = std::process::Command::new("cargo");
-            command.arg("run").arg("-p").arg(&package_name);
-            if *release {
-                command.arg("--release");
+            commands::watch_day(*day, year, release)?;
+        }
+        Commands::Example { command } => match command {
+            ExampleCommands::Add {
+                day,
+                year,
+                part1,
+                part2,
+                clipboard,
+            } => {
+                let year = year.unwrap_or(default_year);
+                resolve_event(year)?.check_day(*day)?;
+
+                commands::add_example(*day, year, part1.as_deref(), part2.as_deref(), *clipboard)?;
             }
+            ExampleCommands::Sync { day, year } => {
+                let year = year.unwrap_or(default_year);
+                resolve_event(year)?.check_day(*day)?;
 
-            let status = command
-                .status()
-                .with_context(|| "Échec de l'exécution de la commande cargo run")?;
-
-            if !status.success() {
-                println!("❌ Le scaffold pour le jour {} de l'année {} n'a pas été trouvé ou une erreur est survenue lors de l'exécution.", day, year);
-                return Err(anyhow::anyhow!(
-                    "La commande cargo run a échoué avec le statut {}",
-                    status
-                ));
+                commands::sync_examples(*day, year)?;
+            }
+        },
+        Commands::Test {
+            day,
+            year,
+            release,
+            no_release,
+        } => {
+            let year = year.unwrap_or(default_year);
+            let release = config.defaults.resolve_release(*release, *no_release);
+            let event = resolve_event(year)?;
+
+            let days: Vec<u8> = match day {
+                Some(day) => {
+                    event.check_day(*day)?;
+                    vec![*day]
+                }
+                None => (1..=event.days)
+                    .filter(|day| utils::day_path(*day, year).exists())
+                    .collect(),
+            };
+            if days.is_empty() {
+                println!("📊 Aucun jour trouvé pour l'année {}", year);
+                return Ok(());
             }
+            println!(
+                "🧪 Tests de {} jour(s) de l'année {}...\n",
+                days.len(),
+                year
+            );
+            commands::test_days(year, &days, release)?;
         }
         Commands::RunAll {
             year,
             release,
+            no_release,
             summary_only,
+            no_summary_only,
+            part,
+            timeout,
+            jobs,
+            json,
+            verbose,
         } => {
-            let current_year = chrono::Utc::now().year() as u16;
-            let year = year.unwrap_or(current_year);
+            let year = year.unwrap_or(default_year);
+            let release = config.defaults.resolve_release(*release, *no_release);
+            let summary_only = config
+                .defaults
+                .resolve_summary_only(*summary_only, *no_summary_only);
+            resolve_event(year)?;
+
+            if !*json {
+                let mode = if release { " (mode release)" } else { "" };
+                println!("🎄 Lancement de tous les jours de {}{}...", year, mode);
+            }
+            commands::run_all(
+                year,
+                commands::RunAllOptions {
+                    release,
+                    summary_only,
+                    part: *part,
+                    timeout: *timeout,
+                    jobs: usize::from(*jobs),
+                    json: *json,
+                    verbose: *verbose,
+                },
+            )?;
+        }
+        Commands::Readme {
+            year,
+            run,
+            release,
+            no_release,
+            file,
+        } => {
+            let release = config.defaults.resolve_release(*release, *no_release);
+            if let Some(year) = year {
+                resolve_event(*year)?;
+            }
+
+            commands::update_readme(*year, *run, release, file)?;
+        }
+        Commands::Chart { year, from, output } => {
+            let years = if year.is_empty() && from.is_empty() {
+                vec![default_year]
+            } else {
+                year.clone()
+            };
+            for year in &years {
+                resolve_event(*year)?;
+            }
+
+            commands::chart(&years, from, output)?;
+        }
+        Commands::Tui {
+            year,
+            release,
+            no_release,
+        } => {
+            let year = year.unwrap_or(default_year);
+            let release = config.defaults.resolve_release(*release, *no_release);
+            resolve_event(year)?;
 
-            let mode = if *release { " (mode release)" } else { "" };
-            println!("🎄 Lancement de tous les jours de {}{}...", year, mode);
-            commands::run_all(year, *release, *summary_only)?;
+            tui::run(year, release)?;
+        }
+        Commands::Doctor { fix } => {
+            println!("🩺 Diagnostic du workspace...");
+            commands::doctor(*fix)?;
         }
+        Commands::Config { command } => match command {
+            ConfigCommands::Get { key } => {
+                let layers = config::Layers::load()?;
+                match layers.get(key) {
+                    Some(value) => println!("{}", value),
+                    None => return Err(anyhow::anyhow!("La clé {} n'est pas définie", key)),
+                }
+            }
+            ConfigCommands::Set { key, value, global } => {
+                let path = if *global {
+                    config::global_config_path().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Impossible de déterminer le répertoire de configuration global"
+                        )
+                    })?
+                } else {
+                    std::path::PathBuf::from(config::CONFIG_FILE)
+                };
+                config::set_value(&path, key, value)?;
+                println!("✅ {} = {} écrit dans {:?}", key, value, path);
+            }
+            ConfigCommands::List => {
+                for (key, value, source) in config::Layers::load()?.list() {
+                    println!("{} = {}  # {}", key, value, source);
+                }
+            }
+        },
+        Commands::Deps { command } => match command {
+            DepsCommands::Sync => {
+                println!(
+                    "📦 Synchronisation des dépendances avec {}...",
+                    config::CONFIG_FILE
+                );
+                commands::sync_dependencies()?;
+            }
+        },
+        Commands::Inputs { command } => match command {
+            InputsCommands::Keygen { output } => {
+                commands::generate_inputs_key(output.as_deref())?;
+            }
+            InputsCommands::Encrypt { day, year, force } => {
+                commands::encrypt_inputs(*year, *day, *force)?;
+            }
+            InputsCommands::Decrypt { day, year, force } => {
+                commands::decrypt_inputs(*year, *day, *force)?;
+            }
+            InputsCommands::Push {
+                to,
+                day,
+                year,
+                force,
+            } => {
+                let direction = commands::SyncDirection::Push;
+                commands::sync_inputs(direction, to.as_deref(), *year, *day, *force)?;
+            }
+            InputsCommands::Pull {
+                from,
+                day,
+                year,
+                force,
+            } => {
+                let direction = commands::SyncDirection::Pull;
+                commands::sync_inputs(direction, from.as_deref(), *year, *day, *force)?;
+            }
+        },
     }
 
     Ok(())
 }
 
-use anyhow::Context;
+/// Résout l'édition d'une année et signale si son nombre de jours n'est pas confirmé
+fn resolve_event(year: u16) -> Result<calendar::Event> {
+    let event = calendar::Event::for_year(year)?;
+    if let Some(warning) = event.warning() {
+        eprintln!("⚠️  {}", warning);
+    }
+    Ok(event)
+}
 
 #[cfg(test)]
 mod tests {
@@ -163,12 +712,19 @@ mod tests {
 
         env::set_current_dir(temp_dir.path())
             .expect("Impossible de changer de répertoire vers le temp_dir");
+        // Configuration globale isolée, pour ne jamais lire ni écrire celle de l'utilisateur
+        let original_config = env::var_os("XDG_CONFIG_HOME");
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path().join("config"));
 
         let result = std::panic::catch_unwind(|| test(&temp_dir));
 
         // Toujours essayer de revenir au répertoire d'origine
         // Ignorer les erreurs si le répertoire n'existe plus
         let _ = env::set_current_dir(&original_dir);
+        match original_config {
+            Some(dir) => env::set_var("XDG_CONFIG_HOME", dir),
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
 
         if let Err(e) = result {
             std::panic::resume_unwind(e);
@@ -225,7 +781,7 @@ mod tests {
     #[serial]
     fn test_initialize_workspace() {
         with_temp_dir(|temp_dir| {
-            let result = commands::initialize_workspace();
+            let result = commands::initialize_workspace(false);
             assert!(result.is_ok());
 
             assert!(temp_dir.path().join("Cargo.toml").exists());
@@ -238,6 +794,148 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn test_initialize_workspace_merges_existing_files() {
+        with_temp_dir(|temp_dir| {
+            fs::write(
+                "Cargo.toml",
+                "# Mon workspace\n[workspace]\nmembers = [\"mush\"]\nresolver = \"2\"\n",
+            )
+            .expect("Impossible d'écrire Cargo.toml");
+            fs::write(".gitignore", "/target\nnotes.md").expect("Impossible d'écrire .gitignore");
+            fs::write(".env", "OTHER=1\n").expect("Impossible d'écrire .env");
+
+            commands::initialize_workspace(true).expect("Initialisation échouée");
+
+            let cargo_content = fs::read_to_string(temp_dir.path().join("Cargo.toml"))
+                .expect("Impossible de lire Cargo.toml");
+            assert!(cargo_content.starts_with("# Mon workspace"));
+            assert!(cargo_content.contains("\"mush\", \"solutions/*/*\""));
+
+            let gitignore =
+                fs::read_to_string(".gitignore").expect("Impossible de lire .gitignore");
+            assert!(gitignore.starts_with("/target\nnotes.md\n"));
+            assert!(gitignore.contains("**/input.txt"));
+            assert_eq!(gitignore.matches("/target\n").count(), 2);
+
+            let env_content = fs::read_to_string(".env").expect("Impossible de lire .env");
+            assert_eq!(
+                env_content,
+                "OTHER=1\nAOC_SESSION=your_session_cookie_here\n"
+            );
+
+            // Un second passage ne modifie plus rien
+            commands::initialize_workspace(false).expect("Initialisation échouée");
+            assert_eq!(
+                fs::read_to_string(".env").expect("Impossible de lire .env"),
+                env_content
+            );
+        });
+    }
+
+    #[test]
+    fn test_fill_empty_env_key() {
+        let content = "AOC_SESSION=abc\nAOC_USER_AGENT_CONTACT=\nOTHER=1\n";
+        let filled =
+            workspace::fill_empty_env_key(content, "AOC_USER_AGENT_CONTACT", "me@example.com");
+        assert_eq!(
+            filled,
+            "AOC_SESSION=abc\nAOC_USER_AGENT_CONTACT=me@example.com\nOTHER=1\n"
+        );
+        // La clé ainsi renseignée n'est plus ajoutée une seconde fois
+        assert_eq!(
+            workspace::add_env_keys(&filled, &[("AOC_USER_AGENT_CONTACT", "me@example.com")]),
+            filled
+        );
+
+        // Formes `export` et guillemets vides
+        assert_eq!(
+            workspace::fill_empty_env_key(
+                "export AOC_USER_AGENT_CONTACT=\"\"",
+                "AOC_USER_AGENT_CONTACT",
+                "me"
+            ),
+            "export AOC_USER_AGENT_CONTACT=me"
+        );
+        // Une valeur déjà renseignée est conservée
+        let configured = "AOC_USER_AGENT_CONTACT=other@example.com\n";
+        assert_eq!(
+            workspace::fill_empty_env_key(configured, "AOC_USER_AGENT_CONTACT", "me"),
+            configured
+        );
+    }
+
+    #[test]
+    fn test_examples_add_and_generated_tests() {
+        let temp_dir = TempDir::new().expect("Impossible de créer le répertoire temporaire");
+        let day_path = temp_dir.path();
+        fs::create_dir_all(day_path.join("src")).unwrap();
+        fs::create_dir_all(day_path.join("examples")).unwrap();
+        fs::write(day_path.join("examples/1.txt"), "").unwrap();
+        fs::write(day_path.join("examples.toml"), examples::EXAMPLES_TEMPLATE).unwrap();
+
+        // Le premier exemple, vide, est rempli avant d'en créer un nouveau
+        assert_eq!(
+            examples::add(day_path, "1 2 3\n", Some("6"), None).unwrap(),
+            1
+        );
+        assert_eq!(
+            examples::add(day_path, "4 5\n", Some("9"), Some("20")).unwrap(),
+            2
+        );
+        assert_eq!(
+            fs::read_to_string(day_path.join("examples/2.txt")).unwrap(),
+            "4 5\n"
+        );
+
+        let toml = fs::read_to_string(day_path.join("examples.toml")).unwrap();
+        assert!(toml.starts_with("# Réponses attendues"));
+        let list = examples::load(day_path).unwrap();
+        assert_eq!(
+            list,
+            vec![
+                examples::Example {
+                    number: 1,
+                    part1: Some("6".into()),
+                    part2: None
+                },
+                examples::Example {
+                    number: 2,
+                    part1: Some("9".into()),
+                    part2: Some("20".into())
+                },
+            ]
+        );
+
+        let tests = fs::read_to_string(day_path.join("src/examples.rs")).unwrap();
+        assert!(tests.contains("example!(part1_example_1, part1, \"1.txt\", \"6\");"));
+        assert!(tests.contains("example!(part2_example_2, part2, \"2.txt\", \"20\");"));
+        assert!(!tests.contains("part2_example_1"));
+        assert!(!examples::is_outdated(day_path).unwrap());
+
+        // Réponses entières acceptées, clés non numériques refusées
+        assert_eq!(
+            examples::parse("[3]\npart2 = 281\n").unwrap()[0]
+                .part2
+                .as_deref(),
+            Some("281")
+        );
+        assert!(examples::parse("[premier]\npart1 = \"1\"\n").is_err());
+
+        assert_eq!(
+            examples::input_path(day_path, 2).unwrap(),
+            day_path.join("examples/2.txt")
+        );
+        assert!(examples::input_path(day_path, 5).is_err());
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = utils::line_diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(diff, vec!["- b", "+ d"]);
+    }
+
     #[test]
     #[serial]
     fn test_create_scaffold_structure() {
@@ -254,7 +952,9 @@ mod tests {
             assert!(day_path.join("Cargo.toml").exists());
             assert!(day_path.join("src/main.rs").exists());
             assert!(day_path.join("input.txt").exists());
-            assert!(day_path.join("example.txt").exists());
+            assert!(day_path.join("examples/1.txt").exists());
+            assert!(day_path.join("examples.toml").exists());
+            assert!(day_path.join("src/examples.rs").exists());
 
             // Vérifier le contenu du Cargo.toml
             let cargo_content = fs::read_to_string(day_path.join("Cargo.toml"))
@@ -268,7 +968,9 @@ mod tests {
                 .expect("Impossible de lire main.rs");
             assert!(main_content.contains("fn part1"));
             assert!(main_content.contains("fn part2"));
-            assert!(main_content.contains("#[cfg(test)]"));
+            assert!(main_content.contains("#[cfg(test)]\nmod examples;"));
+            assert!(main_content.contains("AOC_INPUT"));
+            assert!(!main_content.contains("include_str!(\"../input.txt\")"));
 
             env::remove_var("AOC_SESSION");
         });
@@ -330,83 +1032,1233 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_fetch_input_with_mock_server() {
-        use mockito::Server;
-
-        env::set_var("AOC_SESSION", "test_cookie");
-
-        let mut server = Server::new();
-        let mock = server
-            .mock("GET", "/2024/day/1/input")
-            .match_header("cookie", "session=test_cookie")
-            .match_header(
-                "user-agent",
-                "github.com/cmoron/aoc-rustdolph by cyril.moron@gmail.com",
+    fn test_create_scaffold_uses_config() {
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", "test_session_cookie");
+            fs::write(
+                "mush.toml",
+                r#"[scaffold]
+edition = "2024"
+
+[scaffold.dependencies]
+serde = { version = "1", features = ["derive"] }
+
+[scaffold.lints.rust]
+unused_variables = "allow"
+"#,
+            )
+            .expect("Impossible d'écrire mush.toml");
+
+            let _ = commands::create_scaffold(1, 2024);
+
+            let cargo_content = fs::read_to_string("solutions/2024/day01/Cargo.toml")
+                .expect("Impossible de lire Cargo.toml");
+            assert!(cargo_content.contains("edition = \"2024\""));
+            assert!(cargo_content.contains("serde = { version = \"1\", features = [\"derive\"] }"));
+            assert!(cargo_content.contains("[lints.rust]"));
+            assert!(!cargo_content.contains("itertools"));
+
+            env::remove_var("AOC_SESSION");
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_sync_dependencies_updates_existing_days() {
+        with_temp_dir(|_temp_dir| {
+            fs::create_dir_all("solutions/2023/day05").expect("Impossible de créer le jour");
+            fs::write(
+                "solutions/2023/day05/Cargo.toml",
+                r#"[package]
+name = "day05-2023"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+itertools = "0.10.5"
+rayon = "1.8"
+
+[lints.rust]
+unsafe_code = "forbid"
+"#,
+            )
+            .expect("Impossible d'écrire Cargo.toml");
+            fs::write("Cargo.toml", "[workspace]\nmembers = [\"solutions/*/*\"]\n")
+                .expect("Impossible d'écrire Cargo.toml");
+            fs::write(
+                "mush.toml",
+                "[scaffold.dependencies]\nitertools = \"0.14\"\n\n[scaffold.profile.release]\ndebug = true\n\n[scaffold.lints.clippy]\nneedless_range_loop = \"allow\"\n",
+            )
+            .expect("Impossible d'écrire mush.toml");
+
+            commands::sync_dependencies().expect("La synchronisation a échoué");
+
+            let day_content = fs::read_to_string("solutions/2023/day05/Cargo.toml")
+                .expect("Impossible de lire Cargo.toml");
+            assert!(day_content.contains("itertools = \"0.14\""));
+            assert!(day_content.contains("rayon = \"1.8\""));
+            assert!(day_content.contains("needless_range_loop = \"allow\""));
+            assert!(day_content.contains("unsafe_code = \"forbid\""));
+
+            // Une dépendance ou un lint retiré de mush.toml disparaît, sauf si elle a été ajoutée à la main
+            fs::write("mush.toml", "[scaffold.dependencies]\nregex = \"1.12\"\n")
+                .expect("Impossible d'écrire mush.toml");
+            commands::sync_dependencies().expect("La synchronisation a échoué");
+            let day_content = fs::read_to_string("solutions/2023/day05/Cargo.toml")
+                .expect("Impossible de lire Cargo.toml");
+            assert!(!day_content.contains("itertools"));
+            assert!(day_content.contains("regex = \"1.12\""));
+            assert!(day_content.contains("rayon = \"1.8\""));
+            assert!(!day_content.contains("clippy"));
+            assert!(day_content.contains("[lints.rust]\nunsafe_code = \"forbid\""));
+
+            // Sans aucun lint restant, la table `[lints]` disparaît
+            let scaffold = config::ScaffoldConfig::default();
+            let mut with_lints = config::ScaffoldConfig::default();
+            with_lints
+                .lints
+                .insert("clippy".into(), toml::Value::Table(toml::Table::new()));
+            let synced = manifest::day_manifest("day01-2024", &with_lints).unwrap();
+            assert!(synced.contains("[lints"));
+            let synced = manifest::sync_day_manifest(&synced, &scaffold).unwrap();
+            assert!(!synced.contains("lints]"), "{}", synced);
+
+            let root_content =
+                fs::read_to_string("Cargo.toml").expect("Impossible de lire Cargo.toml");
+            assert!(root_content.contains("[profile.release]"));
+            assert!(root_content.contains("debug = true"));
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_layers_priority() {
+        with_temp_dir(|temp_dir| {
+            let global_dir = temp_dir.path().join("global");
+            fs::create_dir_all(global_dir.join("mush")).expect("Impossible de créer le répertoire");
+            fs::write(
+                global_dir.join("mush/config.toml"),
+                "[defaults]\nyear = 2020\n\n[scaffold]\nedition = \"2018\"\n",
+            )
+            .expect("Impossible d'écrire la configuration globale");
+            fs::write("mush.toml", "[defaults]\nyear = 2022\n")
+                .expect("Impossible d'écrire mush.toml");
+            env::set_var("XDG_CONFIG_HOME", &global_dir);
+            env::set_var("MUSH_DEFAULTS_RELEASE", "true");
+
+            let config = config::Config::load().expect("Configuration invalide");
+            assert_eq!(config.defaults.year, Some(2022));
+            assert!(config.defaults.release);
+            assert_eq!(config.scaffold.edition, "2018");
+            assert!(config.scaffold.dependencies.contains_key("itertools"));
+
+            let layers = config::Layers::load().expect("Configuration invalide");
+            let list = layers.list();
+            let source_of = |key: &str| {
+                list.iter()
+                    .find(|(k, _, _)| k == key)
+                    .map(|(_, _, source)| source.clone())
+            };
+            assert_eq!(source_of("defaults.year").as_deref(), Some("mush.toml"));
+            assert_eq!(
+                source_of("defaults.release").as_deref(),
+                Some("environnement")
+            );
+            assert_eq!(source_of("aoc.base_url").as_deref(), Some("défaut"));
+
+            // Les variables numériques des clés de type texte restent des chaînes
+            env::set_var("MUSH_SCAFFOLD_EDITION", "2021");
+            env::set_var("MUSH_AOC_TIMEOUT", "30");
+            env::set_var("AOC_USER_AGENT_CONTACT", "12345");
+            let config = config::Config::load().expect("Configuration invalide");
+            assert_eq!(config.scaffold.edition, "2021");
+            assert_eq!(config.aoc.timeout, "30");
+            assert_eq!(config.aoc.contact, "12345");
+            assert_eq!(config.defaults.year, Some(2022));
+
+            env::remove_var("XDG_CONFIG_HOME");
+            env::remove_var("MUSH_DEFAULTS_RELEASE");
+            env::remove_var("MUSH_SCAFFOLD_EDITION");
+            env::remove_var("MUSH_AOC_TIMEOUT");
+            env::remove_var("AOC_USER_AGENT_CONTACT");
+        });
+    }
+
+    #[test]
+    fn test_no_flags_override_defaults() {
+        let defaults = config::DefaultsConfig {
+            release: true,
+            ..Default::default()
+        };
+        assert!(defaults.resolve_release(false, false));
+        assert!(!defaults.resolve_release(false, true));
+
+        // La dernière des deux options l'emporte
+        let cli = Cli::try_parse_from(["mush", "run", "-d", "1", "--no-release", "--release"])
+            .expect("Arguments refusés");
+        let Commands::Run {
+            release,
+            no_release,
+            ..
+        } = cli.command
+        else {
+            panic!("Commande inattendue");
+        };
+        assert!(release && !no_release);
+        assert!(config::DefaultsConfig::default().resolve_release(release, no_release));
+
+        let defaults = config::DefaultsConfig {
+            summary_only: true,
+            ..Default::default()
+        };
+        assert!(defaults.resolve_summary_only(false, false));
+        assert!(!defaults.resolve_summary_only(false, true));
+        let cli = Cli::try_parse_from(["mush", "run-all", "--summary-only", "--no-summary-only"])
+            .expect("Arguments refusés");
+        let Commands::RunAll {
+            summary_only,
+            no_summary_only,
+            ..
+        } = cli.command
+        else {
+            panic!("Commande inattendue");
+        };
+        assert!(!defaults.resolve_summary_only(summary_only, no_summary_only));
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_set_value() {
+        with_temp_dir(|_temp_dir| {
+            let path = std::path::Path::new("mush.toml");
+            fs::write(path, "# Mon année\n[defaults]\nrelease = true\n")
+                .expect("Impossible d'écrire mush.toml");
+
+            config::set_value(path, "defaults.year", "2023").expect("Écriture refusée");
+            config::set_value(path, "scaffold.dependencies.rayon", "1.10")
+                .expect("Écriture refusée");
+            assert!(config::set_value(path, "defaults.unknown", "1").is_err());
+
+            let content = fs::read_to_string(path).expect("Impossible de lire mush.toml");
+            assert!(content.starts_with("# Mon année"));
+            assert!(content.contains("year = 2023"));
+            assert!(!content.contains("unknown"));
+
+            let layers = config::Layers::load().expect("Configuration invalide");
+            assert_eq!(
+                layers.get("scaffold.dependencies.rayon"),
+                Some(toml::Value::String("1.10".into()))
+            );
+
+            // Une valeur numérique reste une chaîne pour une clé de type texte
+            config::set_value(path, "scaffold.edition", "2024").expect("Écriture refusée");
+            let content = fs::read_to_string(path).expect("Impossible de lire mush.toml");
+            assert!(content.contains("edition = \"2024\""));
+
+            // Une clé invalide déjà présente n'empêche pas d'écrire les autres
+            fs::write(path, "[defaults]\nbogus = 1\n").expect("Impossible d'écrire mush.toml");
+            config::set_value(path, "defaults.year", "2024").expect("Écriture refusée");
+            assert!(config::set_value(path, "defaults.year", "abc").is_err());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_doctor_detects_and_fixes_problems() {
+        with_temp_dir(|_temp_dir| {
+            fs::write("Cargo.toml", "[workspace]\nmembers = [\"mush\"]\n")
+                .expect("Impossible d'écrire Cargo.toml");
+            fs::write(".env", "AOC_SESSION=your_session_cookie_here\n")
+                .expect("Impossible d'écrire .env");
+            fs::create_dir_all("solutions/2022/day03").expect("Impossible de créer le jour");
+            fs::write(
+                "solutions/2022/day03/Cargo.toml",
+                "[package]\nname = \"day3\"\nversion = \"0.1.0\"\n",
+            )
+            .expect("Impossible d'écrire Cargo.toml");
+            fs::write("solutions/2022/day03/input.txt", "1\n2\n")
+                .expect("Impossible d'écrire l'input");
+
+            let findings = doctor::diagnose().expect("Diagnostic échoué");
+            let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+            assert!(messages
+                .iter()
+                .any(|m| m.contains("n'inclut pas les solutions")));
+            assert!(messages.iter().any(|m| m.contains("valeur d'exemple")));
+            assert!(messages.iter().any(|m| m.contains("User-Agent")));
+            assert!(messages.iter().any(|m| m.contains("example.txt")));
+            assert!(messages.iter().any(|m| m.contains("\"day3\"")));
+            assert!(!messages.iter().any(|m| m.contains("input.txt")));
+
+            for fix in findings.iter().filter_map(|f| f.fix.as_ref()) {
+                doctor::apply(fix).expect("Correction échouée");
+            }
+
+            let cargo_content =
+                fs::read_to_string("Cargo.toml").expect("Impossible de lire Cargo.toml");
+            assert!(cargo_content.contains("\"mush\", \"solutions/*/*\""));
+            let day_content = fs::read_to_string("solutions/2022/day03/Cargo.toml")
+                .expect("Impossible de lire Cargo.toml");
+            assert!(day_content.contains("name = \"day03-2022\""));
+
+            let remaining = doctor::diagnose().expect("Diagnostic échoué");
+            assert!(remaining.iter().all(|f| f.fix.is_none()));
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_inputs_encrypt_and_transparent_decrypt() {
+        use inputs::Outcome;
+
+        with_temp_dir(|temp_dir| {
+            env::set_var("XDG_CONFIG_HOME", temp_dir.path().join("config"));
+            let inputs_config = config::InputsConfig::default();
+            let key_path = inputs::key_path(&inputs_config).unwrap();
+            inputs::generate_key(&key_path).expect("Génération de la clé échouée");
+            assert!(inputs::generate_key(&key_path).is_err());
+            let identity = inputs::load_identity(&inputs_config).expect("Clé illisible");
+            let recipients = inputs::recipients(&identity, &inputs_config).unwrap();
+
+            let day_path = std::path::Path::new("solutions/2024/day01");
+            fs::create_dir_all(day_path).unwrap();
+            fs::write(day_path.join("input.txt"), "1 2\n3 4\n").unwrap();
+
+            let encrypt = || inputs::encrypt_day(day_path, &identity, &recipients, false);
+            assert_eq!(encrypt().unwrap(), Outcome::Written);
+            let encrypted = fs::read_to_string(day_path.join("input.txt.age")).unwrap();
+            assert!(encrypted.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+            assert!(!encrypted.contains("1 2"));
+            // Rechiffrer le même input ne modifie pas le fichier versionné
+            assert_eq!(encrypt().unwrap(), Outcome::Unchanged);
+            assert_eq!(
+                fs::read_to_string(day_path.join("input.txt.age")).unwrap(),
+                encrypted
+            );
+
+            // `run` déchiffre l'input quand il manque
+            fs::remove_file(day_path.join("input.txt")).unwrap();
+            assert!(inputs::ensure_plaintext(day_path).unwrap());
+            assert_eq!(
+                fs::read_to_string(day_path.join("input.txt")).unwrap(),
+                "1 2\n3 4\n"
+            );
+            assert!(!inputs::ensure_plaintext(day_path).unwrap());
+
+            fs::write(day_path.join("input.txt"), "modifié\n").unwrap();
+            assert_eq!(
+                inputs::decrypt_day(day_path, &identity, false).unwrap(),
+                Outcome::Conflict
+            );
+
+            // Une autre clé (AOC_INPUTS_KEY, prioritaire) ne peut pas déchiffrer
+            let other = inputs::generate_key(&temp_dir.path().join("other.key")).unwrap();
+            let other_key = fs::read_to_string(temp_dir.path().join("other.key")).unwrap();
+            assert!(other_key.contains(&other.to_string()));
+            env::set_var(inputs::KEY_ENV, other_key);
+            let other_identity = inputs::load_identity(&inputs_config).unwrap();
+            assert!(inputs::decrypt_day(day_path, &other_identity, true).is_err());
+
+            env::remove_var(inputs::KEY_ENV);
+            env::remove_var("XDG_CONFIG_HOME");
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_inputs_push_pull_shared_dir() {
+        use inputs::Outcome;
+
+        with_temp_dir(|temp_dir| {
+            let shared = temp_dir.path().join("partage");
+            fs::write(
+                "mush.toml",
+                format!(
+                    "[inputs]\nshared_dir = {:?}\n",
+                    shared.display().to_string()
+                ),
+            )
+            .unwrap();
+            for (day, input) in [("01", "1 2\n"), ("02", "3 4\n")] {
+                fs::create_dir_all(format!("solutions/2024/day{}", day)).unwrap();
+                fs::write(format!("solutions/2024/day{}/input.txt", day), input).unwrap();
+            }
+
+            let push =
+                || commands::sync_inputs(commands::SyncDirection::Push, None, None, None, false);
+            push().expect("Envoi échoué");
+            let shared_day = inputs::shared_day_path(&shared, 2024, 1);
+            assert_eq!(
+                fs::read_to_string(shared_day.join("input.txt")).unwrap(),
+                "1 2\n"
+            );
+            assert_eq!(
+                fs::read_to_string(shared_day.join("input.txt.sha256")).unwrap(),
+                format!("{}  input.txt\n", inputs::sha256_hex(b"1 2\n"))
+            );
+            push().expect("Un second envoi ne change rien");
+
+            // Un input modifié des deux côtés est un conflit, sauf avec --force
+            let local_day = std::path::Path::new("solutions/2024/day02");
+            fs::write(local_day.join("input.txt"), "autre\n").unwrap();
+            assert!(push().is_err());
+            let shared_day2 = inputs::shared_day_path(&shared, 2024, 2);
+            assert_eq!(
+                inputs::pull_day(&shared_day2, local_day, false).unwrap(),
+                Outcome::Conflict
+            );
+            assert_eq!(
+                inputs::pull_day(&shared_day2, local_day, true).unwrap(),
+                Outcome::Written
+            );
+            assert_eq!(
+                fs::read_to_string(local_day.join("input.txt")).unwrap(),
+                "3 4\n"
+            );
+
+            // Un input partagé qui ne correspond pas à sa somme n'est jamais récupéré
+            fs::write(shared_day.join("input.txt"), "altéré\n").unwrap();
+            fs::remove_file("solutions/2024/day01/input.txt").unwrap();
+            assert_eq!(
+                inputs::pull_day(
+                    &shared_day,
+                    std::path::Path::new("solutions/2024/day01"),
+                    true
+                )
+                .unwrap(),
+                Outcome::Corrupted
+            );
+            assert!(commands::sync_inputs(
+                commands::SyncDirection::Pull,
+                Some(&shared),
+                Some(2024),
+                None,
+                false
+            )
+            .is_err());
+            assert!(!std::path::Path::new("solutions/2024/day01/input.txt").exists());
+
+            // Les inputs partagés des jours non créés sont listés pour être signalés
+            fs::remove_dir_all("solutions/2024/day02").unwrap();
+            assert_eq!(
+                inputs::shared_days(&shared).unwrap(),
+                vec![(2024, 1), (2024, 2)]
+            );
+
+            // `pull` accepte aussi `--to`, comme `push`
+            let cli = Cli::try_parse_from(["mush", "inputs", "pull", "--to", "partage"])
+                .expect("Arguments refusés");
+            let Commands::Inputs {
+                command: InputsCommands::Pull { from, .. },
+            } = cli.command
+            else {
+                panic!("Commande inattendue");
+            };
+            assert_eq!(from, Some(std::path::PathBuf::from("partage")));
+        });
+    }
+
+    #[test]
+    fn test_part_result_distinguishes_skipped_and_missing() {
+        use results::{Answer, PartResult};
+        use std::time::Duration;
+
+        let output = "Part 2: 42\nTime: 1.5000ms\n";
+
+        assert_eq!(
+            PartResult::from_output(output, 1, Some(2)),
+            PartResult::Skipped
+        );
+        assert_eq!(
+            PartResult::from_output(output, 1, None),
+            PartResult::Missing
+        );
+        assert_eq!(
+            PartResult::from_output(output, 2, Some(2)),
+            PartResult::Answered {
+                answer: Answer::Integer(42),
+                time: Some(Duration::from_micros(1500))
+            }
+        );
+        // Un jour qui ignore AOC_PART affiche quand même l'autre partie
+        let output = "Part 1: 7\nPart 2: 42\n";
+        assert_eq!(
+            PartResult::from_output(output, 1, Some(2)),
+            PartResult::Answered {
+                answer: Answer::Integer(7),
+                time: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_part_answers_and_time_units() {
+        use results::{parse_part, parse_time, Answer};
+        use std::time::Duration;
+
+        assert_eq!(parse_time("850ns"), Some(Duration::from_nanos(850)));
+        assert_eq!(parse_time(" 12.5µs"), Some(Duration::from_nanos(12_500)));
+        assert_eq!(parse_time("12.5us"), Some(Duration::from_nanos(12_500)));
+        assert_eq!(parse_time("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_time("2"), Some(Duration::from_millis(2)));
+        assert_eq!(parse_time("3 jours"), None);
+
+        let output = "Part 1: abc:def\nTime: 3.2µs\nPart 2:\n\n#..#\n####\n#..#\n\nTime: 1.5s\n";
+        assert_eq!(
+            parse_part(output, "Part 1"),
+            (
+                Some(Answer::Text("abc:def".into())),
+                Some(Duration::from_nanos(3200))
             )
-            .with_status(200)
-            .with_body("Test input data\n")
-            .create();
+        );
+        let (answer, time) = parse_part(output, "Part 2");
+        let answer = answer.unwrap();
+        assert_eq!(
+            answer,
+            Answer::Art(vec!["#..#".into(), "####".into(), "#..#".into()])
+        );
+        assert_eq!(answer.summary(), "dessin 4x3");
+        assert_eq!(time, Some(Duration::from_millis(1500)));
+        assert_eq!(
+            parse_part("Part 1: 18446744073709551616\n", "Part 1").0,
+            Some(Answer::Integer(18446744073709551616))
+        );
+        // Un zéro initial ou un signe + fait partie de la réponse
+        assert_eq!(
+            Answer::parse("0124515891"),
+            Answer::Text("0124515891".into())
+        );
+        assert_eq!(Answer::parse("+5"), Answer::Text("+5".into()));
+        for answer in [
+            Answer::Integer(-3),
+            Answer::Integer(18446744073709551616),
+            Answer::Text("abc".into()),
+            Answer::Text("0124515891".into()),
+            Answer::Art(vec!["#.".into()]),
+        ] {
+            let json = serde_json::to_string(&answer).unwrap();
+            assert_eq!(serde_json::from_str::<Answer>(&json).unwrap(), answer);
+        }
 
-        let result = fetch::fetch_input_with_base_url(1, 2024, &server.url());
+        assert_eq!(utils::format_duration(Duration::from_nanos(850)), "850 ns");
+        assert_eq!(
+            utils::format_duration(Duration::from_nanos(12_345)),
+            "12.35 µs"
+        );
+        assert_eq!(
+            utils::format_duration(Duration::from_micros(4200)),
+            "4.20 ms"
+        );
+        assert_eq!(
+            utils::format_duration(Duration::from_millis(1500)),
+            "1.50 s"
+        );
+    }
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "Test input data\n");
-        mock.assert();
+    #[test]
+    fn test_ocr_reads_drawn_letters() {
+        use results::{parse_part, Answer};
+
+        // Les polices sont testées dans mush-helpers : seule la lecture de la sortie l'est ici
+        let grid = [
+            ".##...##...##.",
+            "#..#.#..#.#..#",
+            "#..#.#..#.#...",
+            "####.#..#.#...",
+            "#..#.#..#.#..#",
+            "#..#..##...##.",
+        ]
+        .join("\n");
+        let output = format!("Part 2:\n{}\nTime: 12µs\n", grid);
+        assert_eq!(
+            parse_part(&output, "Part 2").0,
+            Some(Answer::Text("AOC".into()))
+        );
+    }
 
-        env::remove_var("AOC_SESSION");
+    #[test]
+    fn test_failure_from_panic_and_compile_output() {
+        use results::Failure;
+
+        let stderr = "\nthread 'main' panicked at src/main.rs:12:5:\nindex out of bounds\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            Failure::from_exit(None, stderr),
+            Failure::Panic {
+                message: "index out of bounds".into(),
+                location: Some("src/main.rs:12:5".into()),
+            }
+        );
+
+        // Format des versions de Rust antérieures à 1.73
+        let stderr = "thread 'main' panicked at 'boom', src/main.rs:3:5\n";
+        assert_eq!(
+            Failure::from_exit(None, stderr),
+            Failure::Panic {
+                message: "boom".into(),
+                location: Some("src/main.rs:3:5".into()),
+            }
+        );
+
+        let stderr = "   Compiling day05-2020 v0.1.0\nerror[E0425]: cannot find value `x`\n --> src/main.rs:4:13\n\nerror: could not compile `day05-2020`\n";
+        assert_eq!(
+            Failure::compile(stderr),
+            Failure::Compile {
+                message: "error[E0425]: cannot find value `x`\n --> src/main.rs:4:13".into()
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_failure_from_exit_code_and_signal() {
+        use results::Failure;
+        use std::process::Command;
+
+        let status = Command::new("sh").args(["-c", "exit 3"]).status().unwrap();
+        assert_eq!(
+            Failure::from_exit(Some(status), ""),
+            Failure::ExitCode { code: 3 }
+        );
+
+        let status = Command::new("sh")
+            .args(["-c", "kill -9 $$"])
+            .status()
+            .unwrap();
+        let failure = Failure::from_exit(Some(status), "");
+        assert_eq!(failure, Failure::Signal { signal: 9 });
+        assert_eq!(failure.label(), "SIGKILL");
+    }
+
+    #[test]
+    fn test_build_output_from_cargo_messages() {
+        let stdout = r#"{"reason":"compiler-artifact","target":{"name":"itertools","kind":["lib"]},"executable":null,"fresh":true}
+{"reason":"compiler-artifact","target":{"name":"day01-2024","kind":["bin"]},"executable":"/ws/target/release/day01-2024","fresh":false}
+{"reason":"compiler-artifact","target":{"name":"day01-2024","kind":["bin"]},"profile":{"test":true},"executable":"/ws/target/release/deps/day01_2024-1a2b","fresh":false}
+{"reason":"compiler-message","target":{"name":"day02-2024","kind":["bin"]},"message":{"level":"warning","rendered":"warning: unused variable\n"}}
+{"reason":"compiler-message","target":{"name":"day02-2024","kind":["bin"]},"message":{"level":"error","rendered":"error[E0425]: cannot find value `x`\n"}}
+{"reason":"build-finished","success":false}"#;
+
+        let build = build::BuildOutput::from_messages(stdout);
+
+        assert_eq!(build.executables.len(), 1);
+        assert_eq!(
+            build.executables["day01-2024"],
+            std::path::PathBuf::from("/ws/target/release/day01-2024")
+        );
+        // L'exécutable de tests (`cargo build --tests`) est rangé à part
+        assert_eq!(
+            build.tests["day01-2024"],
+            std::path::PathBuf::from("/ws/target/release/deps/day01_2024-1a2b")
+        );
+        assert_eq!(
+            build.errors["day02-2024"],
+            "error[E0425]: cannot find value `x`\n"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_collect_results_in_day_order_with_jobs() {
+        with_temp_dir(|_temp_dir| {
+            fs::write(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"solutions/*/*\"]\nresolver = \"2\"\n",
+            )
+            .expect("Impossible d'écrire Cargo.toml");
+            // Le jour 1 finit en dernier : son résultat doit quand même arriver en premier
+            for (day, delay) in [(1, 500), (2, 0), (3, 0)] {
+                let day_path = utils::day_path(day, 2024);
+                fs::create_dir_all(day_path.join("src")).expect("Impossible de créer le jour");
+                fs::write(
+                    day_path.join("Cargo.toml"),
+                    format!(
+                        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+                        utils::package_name(day, 2024)
+                    ),
+                )
+                .expect("Impossible d'écrire Cargo.toml");
+                fs::write(
+                    day_path.join("src/main.rs"),
+                    format!(
+                        "fn main() {{\n    std::thread::sleep(std::time::Duration::from_millis({}));\n    println!(\"Part 1: {}\");\n}}\n",
+                        delay, day
+                    ),
+                )
+                .expect("Impossible d'écrire main.rs");
+            }
+
+            let mut streamed = Vec::new();
+            let (results, _) = commands::collect_results(
+                2024,
+                &[1, 2, 3],
+                commands::RunAllOptions {
+                    jobs: 3,
+                    ..Default::default()
+                },
+                |result| streamed.push(result.day),
+            )
+            .expect("L'exécution a échoué");
+
+            assert_eq!(streamed, vec![1, 2, 3]);
+            let days: Vec<u8> = results.iter().map(|result| result.day).collect();
+            assert_eq!(days, vec![1, 2, 3]);
+            for result in &results {
+                assert_eq!(
+                    result.part1,
+                    results::PartResult::Answered {
+                        answer: results::Answer::Integer(result.day.into()),
+                        time: None
+                    }
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_test_report_parse() {
+        let stdout = "
+running 4 tests
+test tests::test_part1 ... ok
+test tests::test_part2 ... FAILED
+test tests::test_slow ... ignored
+test tests::test_slower ... ignored, trop lent
+
+failures:
+
+---- tests::test_part2 stdout ----
+
+thread 'tests::test_part2' panicked at src/main.rs:40:9:
+assertion `left == right` failed
+  left: 0
+ right: 1
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::test_part2
+
+test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        let report = libtest::TestReport::parse(stdout).unwrap();
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.ignored, 2);
+        assert_eq!(report.total(), 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "tests::test_part2");
+        assert!(report.failures[0].1.contains("right: 1"));
+        assert!(!report.failures[0].1.contains("note:"));
+
+        // Erreur de compilation : aucun test lancé
+        assert_eq!(libtest::TestReport::parse(""), None);
+    }
+
+    #[test]
+    fn test_day_watcher_detects_relevant_changes() {
+        use std::time::Duration;
+
+        let temp_dir = setup_temp_dir();
+        let day_path = temp_dir.path();
+        fs::create_dir(day_path.join("src")).unwrap();
+        fs::write(day_path.join("example.txt"), "").unwrap();
+
+        let watcher = watch::DayWatcher::new(day_path).unwrap();
+
+        // Un fichier hors src/, example.txt et input.txt est ignoré
+        fs::write(day_path.join("notes.md"), "brouillon").unwrap();
+        let changed = watcher
+            .next_change(Some(Duration::from_millis(500)))
+            .unwrap();
+        assert_eq!(changed, None);
+
+        fs::write(day_path.join("example.txt"), "1 2 3").unwrap();
+        let changed = watcher
+            .next_change(Some(Duration::from_secs(5)))
+            .unwrap()
+            .expect("modification non détectée");
+        assert!(changed.iter().all(|path| path.ends_with("example.txt")));
+
+        fs::write(day_path.join("src/main.rs"), "fn main() {}").unwrap();
+        let changed = watcher
+            .next_change(Some(Duration::from_secs(5)))
+            .unwrap()
+            .expect("modification non détectée");
+        assert!(changed.iter().any(|path| path.ends_with("src/main.rs")));
+    }
+
+    #[test]
+    #[serial]
+    fn test_tui_shows_saved_results_offline() {
+        use ratatui::backend::TestBackend;
+        use ratatui::crossterm::event::KeyCode;
+        use ratatui::Terminal;
+
+        with_temp_dir(|_| {
+            let day_path = utils::day_path(2, 2024);
+            fs::create_dir_all(&day_path).unwrap();
+            fs::write(day_path.join("Cargo.toml"), "").unwrap();
+            fs::write(day_path.join("input.txt"), "1 2 3").unwrap();
+
+            let mut saved = results::SavedResults::load(2024).unwrap();
+            saved.merge(&[results::DayResult {
+                day: 2,
+                status: results::DayStatus::Ok,
+                part1: results::PartResult::Answered {
+                    answer: results::Answer::Integer(4242),
+                    time: Some(std::time::Duration::from_micros(500)),
+                },
+                part2: results::PartResult::Missing,
+                usage: None,
+                run_time: Some(std::time::Duration::from_millis(1)),
+                release: false,
+            }]);
+            saved.save().unwrap();
+
+            let mut app = tui::App::load(2024, false).unwrap();
+            assert_eq!(app.cells.len(), 25);
+            assert!(!app.cells[0].scaffolded);
+            assert!(app.cells[1].scaffolded && app.cells[1].has_input);
+
+            // Navigation dans la grille 5x5, sans en sortir
+            assert_eq!(app.handle_key(KeyCode::Right), None);
+            assert_eq!(app.selected_cell().day, 2);
+            app.handle_key(KeyCode::Down);
+            assert_eq!(app.selected_cell().day, 7);
+            app.handle_key(KeyCode::Up);
+            app.handle_key(KeyCode::Up);
+            assert_eq!(app.selected_cell().day, 2);
+            assert_eq!(app.handle_key(KeyCode::Char('r')), Some(tui::Action::Run));
+
+            let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+            terminal.draw(|frame| tui::draw(frame, &app)).unwrap();
+            let screen: String = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect();
+            assert!(screen.contains("Day 25"));
+            assert!(screen.contains("P1 4242"));
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_saved_results_merge_keeps_skipped_parts_and_modes() {
+        use results::{Answer, DayResult, DayStatus, PartResult, SavedResults};
+
+        let answered = |answer| PartResult::Answered {
+            answer: Answer::Integer(answer),
+            time: None,
+        };
+        let day = |day, part1, part2, release| DayResult {
+            day,
+            status: DayStatus::Ok,
+            part1,
+            part2,
+            usage: None,
+            run_time: None,
+            release,
+        };
+
+        let mut saved = SavedResults::default();
+        saved.merge(&[
+            day(1, answered(1), answered(2), true),
+            day(2, answered(3), answered(4), false),
+        ]);
+
+        // `--part 2` en release : la partie 1 précédente est gardée, le jour 2 reste en debug
+        saved.merge(&[day(1, PartResult::Skipped, answered(5), true)]);
+        let day1 = saved.get(1).unwrap();
+        assert_eq!((&day1.part1, &day1.part2), (&answered(1), &answered(5)));
+        assert!(!saved.get(2).unwrap().release);
+
+        // Dans un autre mode, l'ancienne partie n'est pas comparable et n'est pas gardée
+        saved.merge(&[day(1, answered(6), PartResult::Skipped, false)]);
+        assert_eq!(saved.get(1).unwrap().part2, PartResult::Skipped);
+
+        // Les anciens fichiers avaient un seul mode pour toute l'année
+        with_temp_dir(|_| {
+            fs::create_dir_all("target/mush").unwrap();
+            fs::write(
+                SavedResults::path(2023),
+                r#"{"year":2023,"release":true,"days":[{"day":1,"status":{"kind":"ok"},"part1":{"result":"missing"},"part2":{"result":"missing"},"usage":null}]}"#,
+            )
+            .unwrap();
+            assert!(SavedResults::load(2023).unwrap().get(1).unwrap().release);
+        });
+    }
+
+    #[test]
+    fn test_readme_progress_section() {
+        use results::{DayResult, DayStatus, PartResult, SavedResults};
+
+        let saved = SavedResults {
+            year: 2024,
+            days: vec![DayResult {
+                day: 1,
+                status: DayStatus::Ok,
+                part1: PartResult::Answered {
+                    answer: results::Answer::Integer(42),
+                    time: Some(std::time::Duration::from_micros(125)),
+                },
+                part2: PartResult::Missing,
+                usage: None,
+                run_time: None,
+                release: true,
+            }],
+        };
+        let table = readme::render_year(&saved, &[1, 2]);
+        // Les réponses ne sont pas publiées, seulement leur statut
+        assert_eq!(
+            table,
+            "### 2024\n\n\
+             ✅ 1 partie(s) avec une réponse, non vérifiée(s) · temps mesurés en mode release\n\n\
+             | Jour | Partie 1 | Partie 2 | Temps P1 | Temps P2 |\n\
+             |:----:|:--------:|:--------:|---------:|---------:|\n\
+             | [01](solutions/2024/day01/src/main.rs) | ✅ | ❔ | 125.00 µs |  |\n\
+             | [02](solutions/2024/day02/src/main.rs) | — | — |  |  |\n"
+        );
+
+        // Le 0 du template n'est pas compté, les temps debug sont signalés si les modes diffèrent
+        let saved = SavedResults {
+            days: vec![
+                saved.days[0].clone(),
+                DayResult {
+                    day: 2,
+                    part1: PartResult::Answered {
+                        answer: results::Answer::Integer(0),
+                        time: Some(std::time::Duration::from_millis(2)),
+                    },
+                    release: false,
+                    ..saved.days[0].clone()
+                },
+            ],
+            ..saved
+        };
+        let table = readme::render_year(&saved, &[1, 2]);
+        assert!(table.contains("✅ 1 partie(s) avec une réponse"));
+        assert!(table.contains("modes release et debug"));
+        assert!(table
+            .contains("| [02](solutions/2024/day02/src/main.rs) | ❔ | ❔ | 2.00 ms (debug) |  |"));
+
+        let content = "# AoC\n\n<!-- mush:start -->\nancien\n<!-- mush:end -->\n\nFin\n";
+        assert_eq!(
+            readme::replace_section(content, "nouveau\n"),
+            "# AoC\n\n<!-- mush:start -->\nnouveau\n<!-- mush:end -->\n\nFin\n"
+        );
+        // Sans marqueurs, la section est ajoutée en fin de fichier
+        assert_eq!(
+            readme::replace_section("# AoC", "nouveau\n"),
+            "# AoC\n\n<!-- mush:start -->\nnouveau\n<!-- mush:end -->\n"
+        );
+    }
+
+    #[test]
+    fn test_chart_svg() {
+        use chart::Series;
+
+        let series = [
+            Series {
+                label: "2023".into(),
+                days: vec![(1, Some(0.01), Some(10.0)), (2, Some(1.0), None)],
+            },
+            Series {
+                label: "<v2>".into(),
+                days: vec![(1, Some(0.5), Some(0.5))],
+            },
+        ];
+        let svg = chart::render(&series, "Temps par jour");
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        // Une barre par partie ayant un temps, et une graduation par puissance de 10
+        assert_eq!(svg.matches("<title>").count(), 5);
+        for tick in ["1 µs", "10 µs", "100 µs", "1 ms", "10 ms"] {
+            assert!(svg.contains(&format!(">{}</text>", tick)), "{}", tick);
+        }
+        assert!(svg.contains("&lt;v2&gt; P1"));
+        // La barre la plus longue occupe toute la hauteur du graphique
+        assert!(svg.contains("y=\"60.0\" width=\"7\" height=\"260.0\""));
+        // Le plus petit temps (0.01 ms, puissance de 10 exacte) garde une barre visible
+        assert!(svg.contains("width=\"7\" height=\"65.0\""));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+
+        assert_eq!(utils::parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(
+            utils::parse_duration("500ms"),
+            Ok(Duration::from_millis(500))
+        );
+        assert_eq!(utils::parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(
+            utils::parse_duration("1.5s"),
+            Ok(Duration::from_millis(1500))
+        );
+        assert!(utils::parse_duration("10 jours").is_err());
+        assert!(utils::parse_duration("abc").is_err());
+    }
+
+    #[test]
+    fn test_runner_kills_part_exceeding_timeout() {
+        use std::process::Command;
+        use std::time::{Duration, Instant};
+
+        // La partie 1 répond, la partie 2 dépasse le délai
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo 'Part 1: 7'; sleep 5; echo 'Part 2: 8'"]);
+
+        let start = Instant::now();
+        let output = runner::run(&mut command, Some(Duration::from_millis(300))).unwrap();
+
+        assert!(output.timed_out);
+        assert!(output.status.is_none());
+        assert!(start.elapsed() < Duration::from_secs(4));
+        assert_eq!(output.stdout, "Part 1: 7");
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo 'Part 1: 7'; echo 'Part 2: 8'"]);
+        let output = runner::run(&mut command, Some(Duration::from_secs(5))).unwrap();
+        assert!(!output.timed_out);
+        assert!(output.status.is_some_and(|status| status.success()));
+    }
+
+    #[test]
+    fn test_resource_usage() {
+        let usage = runner::ResourceUsage {
+            max_rss_kb: 512,
+            user_time_ms: 0.0,
+            system_time_ms: 0.0,
+        };
+        assert_eq!(usage.memory(), "512 Ko");
+        let usage = runner::ResourceUsage {
+            max_rss_kb: 3 * 1024 + 512,
+            ..usage
+        };
+        assert_eq!(usage.memory(), "3.5 Mo");
+
+        // Sous Linux, wait4 relève le pic mémoire du processus
+        #[cfg(target_os = "linux")]
+        {
+            let mut command = std::process::Command::new("sh");
+            command.args(["-c", "true"]);
+            let output = runner::run(&mut command, None).unwrap();
+            assert!(output.usage.is_some_and(|usage| usage.max_rss_kb > 0));
+        }
+    }
+
+    #[test]
+    fn test_event_days_per_year() {
+        assert_eq!(calendar::Event::for_year(2015).unwrap().days, 25);
+        assert_eq!(calendar::Event::for_year(2024).unwrap().days, 25);
+        assert_eq!(calendar::Event::for_year(2025).unwrap().days, 12);
+        assert!(calendar::Event::for_year(2014).is_err());
+
+        let future = calendar::Event::for_year(2099).unwrap();
+        assert!(!future.known);
+        assert!(future.warning().is_some());
+    }
+
+    #[test]
+    fn test_event_check_day_and_unlock_time() {
+        let event = calendar::Event::for_year(2025).unwrap();
+        assert!(event.check_day(12).is_ok());
+        assert!(event.check_day(13).is_err());
+        assert!(event.check_day(0).is_err());
+
+        // Minuit heure de l'Est correspond à 5h UTC
+        let unlock = event.unlock_time(1).with_timezone(&chrono::Utc);
+        assert_eq!(unlock.to_rfc3339(), "2025-12-01T05:00:00+00:00");
+        assert_eq!(event.progress(3), 25.0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_create_scaffold_rejects_day_outside_event() {
+        with_temp_dir(|_temp_dir| {
+            let result = commands::create_scaffold(13, 2025);
+            assert!(result.is_err());
+            assert!(!std::path::Path::new("solutions/2025/day13").exists());
+        });
+    }
+
+    /// Client HTTP pointant vers un faux serveur Advent of Code
+    fn fake_client(url: &str) -> fetch::Client {
+        fetch::Client::new(&config::AocConfig {
+            base_url: url.to_string(),
+            ..Default::default()
+        })
+        .expect("Client HTTP invalide")
+    }
+
+    #[test]
+    #[serial]
+    fn test_missing_contact_warning_shown_once() {
+        with_temp_dir(|_temp_dir| {
+            let marker = fetch::contact_warning_marker().unwrap();
+            assert!(!marker.exists());
+            assert!(fetch::warn_missing_contact());
+            assert!(marker.exists());
+            // Les commandes suivantes ne le répètent plus
+            assert!(!fetch::warn_missing_contact());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_input_with_fake_server() {
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", "test_cookie");
+
+            let server = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+                .session("test_cookie")
+                .start()
+                .unwrap();
+
+            let result = fake_client(server.url()).fetch_input(1, 2024);
+
+            assert_eq!(result.unwrap(), "fake input line 1\nfake input line 2\n");
+            let requests = server.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].path, "/2024/day/1/input");
+            assert_eq!(requests[0].header("cookie"), Some("session=test_cookie"));
+            // Sans contact configuré, seule l'identité de mush est envoyée
+            assert_eq!(
+                requests[0].header("user-agent"),
+                Some("github.com/cmoron/aoc-rustdolph")
+            );
+
+            env::remove_var("AOC_SESSION");
+        });
     }
 
     #[test]
     #[serial]
     fn test_fetch_input_http_error() {
-        use mockito::Server;
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", "test_cookie");
 
-        env::set_var("AOC_SESSION", "test_cookie");
+            let server = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+                .session("test_cookie")
+                .start()
+                .unwrap();
 
-        let mut server = Server::new();
-        let mock = server
-            .mock("GET", "/2024/day/1/input")
-            .with_status(404)
-            .create();
+            // Pas de fixture pour le jour 2
+            let result = fake_client(server.url()).fetch_input(2, 2024);
 
-        let result = fetch::fetch_input_with_base_url(1, 2024, &server.url());
+            assert!(result.is_err());
+            assert_eq!(server.requests().len(), 1);
 
-        assert!(result.is_err());
-        mock.assert();
+            env::remove_var("AOC_SESSION");
+        });
+    }
 
-        env::remove_var("AOC_SESSION");
+    #[test]
+    #[serial]
+    fn test_fetch_input_locked_day_and_expired_session() {
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", "test_cookie");
+
+            let tomorrow = std::time::SystemTime::now() + std::time::Duration::from_secs(86400);
+            let locked = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+                .session("test_cookie")
+                .unlock_at(2024, 1, tomorrow)
+                .start()
+                .unwrap();
+            let error = fake_client(locked.url()).fetch_input(1, 2024).unwrap_err();
+            assert!(error.to_string().contains("404"));
+
+            let expired = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+                .session("test_cookie")
+                .session_expired(true)
+                .start()
+                .unwrap();
+            let error = fake_client(expired.url()).fetch_input(1, 2024).unwrap_err();
+            assert!(error.to_string().contains("400"));
+
+            env::remove_var("AOC_SESSION");
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_input_uses_configured_client() {
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", mush_fakeaoc::DEFAULT_SESSION);
+            let server = mush_fakeaoc::FakeAoc::new(mush_fakeaoc::FIXTURES)
+                .start()
+                .unwrap();
+
+            // AOC_BASE_URL redirige toutes les requêtes, le contact vient de mush.toml
+            fs::write("mush.toml", "[aoc]\ncontact = \"rudolph@example.com\"\n").unwrap();
+            env::set_var("AOC_BASE_URL", format!("{}/", server.url()));
+            assert!(fetch::fetch_input(1, 2024).is_ok());
+            assert_eq!(
+                server.requests()[0].header("user-agent"),
+                Some("github.com/cmoron/aoc-rustdolph by rudolph@example.com")
+            );
+
+            // AOC_USER_AGENT_CONTACT l'emporte sur mush.toml
+            env::set_var("AOC_USER_AGENT_CONTACT", "comet@example.com");
+            assert!(fetch::fetch_input(1, 2024).is_ok());
+            assert_eq!(
+                server.requests()[1].header("user-agent"),
+                Some("github.com/cmoron/aoc-rustdolph by comet@example.com")
+            );
+            env::remove_var("AOC_USER_AGENT_CONTACT");
+            env::remove_var("AOC_BASE_URL");
+
+            // Un serveur qui ne répond jamais dépasse le délai configuré
+            let silent = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let client = fetch::Client::new(&config::AocConfig {
+                base_url: format!("http://{}", silent.local_addr().unwrap()),
+                timeout: "200ms".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+            let start = std::time::Instant::now();
+            assert!(client.fetch_input(1, 2024).is_err());
+            assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+            env::remove_var("AOC_SESSION");
+        });
     }
 
     #[test]
     #[serial]
     fn test_fetch_input_missing_session() {
-        env::remove_var("AOC_SESSION");
+        with_temp_dir(|_temp_dir| {
+            env::remove_var("AOC_SESSION");
 
-        let result = fetch::fetch_input(1, 2024);
-        assert!(result.is_err());
+            let result = fetch::fetch_input(1, 2024);
+            assert!(result.is_err());
+        });
     }
 
     #[test]
     #[serial]
     fn test_fetch_input_preserves_content() {
-        use mockito::Server;
-
-        env::set_var("AOC_SESSION", "test_cookie");
+        with_temp_dir(|_temp_dir| {
+            env::set_var("AOC_SESSION", mush_fakeaoc::DEFAULT_SESSION);
 
-        let mut server = Server::new();
-        let mock = server
-            .mock("GET", "/2024/day/1/input")
-            .with_status(200)
-            .with_body("Input with trailing whitespace   \n\n\n")
-            .create();
+            let fixtures = TempDir::new().unwrap();
+            fs::create_dir_all(fixtures.path().join("2024/day01")).unwrap();
+            fs::write(
+                fixtures.path().join("2024/day01/input.txt"),
+                "Input with trailing whitespace   \n\n\n",
+            )
+            .unwrap();
+            let server = mush_fakeaoc::FakeAoc::new(fixtures.path()).start().unwrap();
 
-        let result = fetch::fetch_input_with_base_url(1, 2024, &server.url());
+            let result = fake_client(server.url()).fetch_input(1, 2024);
 
-        assert!(result.is_ok());
-        // Vérifier que le contenu est préservé tel quel
-        assert_eq!(result.unwrap(), "Input with trailing whitespace   \n\n\n");
-        mock.assert();
+            // Vérifier que le contenu est préservé tel quel
+            assert_eq!(result.unwrap(), "Input with trailing whitespace   \n\n\n");
 
-        env::remove_var("AOC_SESSION");
+            env::remove_var("AOC_SESSION");
+        });
     }
 }
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+use crate::config::ScaffoldConfig;
+
+/// Génère le `Cargo.toml` d'un jour à partir de la configuration de scaffold.
+pub fn day_manifest(package_name: &str, scaffold: &ScaffoldConfig) -> Result<String> {
+    let base = format!(
+        r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "{}"
+
+[dependencies]
+"#,
+        package_name, scaffold.edition
+    );
+
+    sync_day_manifest(&base, scaffold)
+}
+
+/// Aligne un `Cargo.toml` de jour existant sur la configuration de scaffold.
+///
+/// L'édition, les dépendances configurées et les lints sont remplacés. Les
+/// dépendances et les outils de lints gérés par mush sont listés dans
+/// `[package.metadata.mush]` : ceux retirés de la configuration sont supprimés,
+/// ceux ajoutés à la main et le reste du fichier sont conservés.
+///
+/// # Errors
+///
+/// Retourne une erreur si le manifeste n'est pas un TOML valide.
+pub fn sync_day_manifest(content: &str, scaffold: &ScaffoldConfig) -> Result<String> {
+    let mut doc = parse(content)?;
+
+    doc["package"]["edition"] = toml_edit::value(scaffold.edition.as_str());
+
+    let previous = managed(&doc, "dependencies");
+    let dependencies = doc
+        .entry("dependencies")
+        .or_insert_with(|| Item::Table(Table::new()));
+    for name in &previous {
+        if !scaffold.dependencies.contains_key(name) {
+            if let Some(table) = dependencies.as_table_like_mut() {
+                table.remove(name);
+            }
+        }
+    }
+    for (name, spec) in &scaffold.dependencies {
+        dependencies[name.as_str()] = Item::Value(to_edit_value(spec));
+    }
+
+    let previous = managed(&doc, "lints");
+    let lints = doc.entry("lints").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    if let Some(table) = lints.as_table_like_mut() {
+        for tool in &previous {
+            if !scaffold.lints.contains_key(tool) {
+                table.remove(tool);
+            }
+        }
+    }
+    for (tool, item) in to_sections(&scaffold.lints).iter() {
+        lints[tool] = item.clone();
+    }
+    if lints.as_table_like().is_some_and(|table| table.is_empty()) {
+        doc.remove("lints");
+    }
+
+    let metadata = doc["package"]["metadata"].or_insert({
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    let mush = metadata["mush"].or_insert(Item::Table(Table::new()));
+    mush["dependencies"] = toml_edit::value(names(&scaffold.dependencies));
+    mush["lints"] = toml_edit::value(names(&scaffold.lints));
+
+    Ok(doc.to_string())
+}
+
+/// Applique les profils configurés au `Cargo.toml` racine du workspace.
+///
+/// Cargo ignore les profils déclarés dans les membres d'un workspace, ils sont
+/// donc écrits à la racine plutôt que dans le manifeste de chaque jour.
+///
+/// # Errors
+///
+/// Retourne une erreur si le manifeste n'est pas un TOML valide.
+pub fn sync_root_profiles(content: &str, scaffold: &ScaffoldConfig) -> Result<String> {
+    let mut doc = parse(content)?;
+
+    if scaffold.profile.is_empty() {
+        return Ok(doc.to_string());
+    }
+
+    let profiles = doc.entry("profile").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    for (name, settings) in &scaffold.profile {
+        let Some(settings) = settings.as_table() else {
+            continue;
+        };
+        let profile = profiles[name.as_str()].or_insert(Item::Table(Table::new()));
+        for (key, value) in settings {
+            profile[key.as_str()] = Item::Value(to_edit_value(value));
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+fn parse(content: &str) -> Result<DocumentMut> {
+    content
+        .parse::<DocumentMut>()
+        .context("Le Cargo.toml n'est pas un fichier TOML valide")
+}
+
+/// Convertit une table en sections `[parent.cle]` dont les valeurs sont en ligne
+/// Noms gérés par mush listés dans `[package.metadata.mush]`, aucun si la liste est absente
+fn managed(doc: &DocumentMut, key: &str) -> Vec<String> {
+    doc.get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("mush"))
+        .and_then(|mush| mush.get(key))
+        .and_then(Item::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn names(table: &toml::Table) -> toml_edit::Array {
+    table.keys().map(String::as_str).collect()
+}
+
+fn to_sections(table: &toml::Table) -> Table {
+    let mut sections = Table::new();
+    sections.set_implicit(true);
+    for (key, value) in table {
+        let item = match value {
+            toml::Value::Table(sub) => Item::Table(
+                sub.iter()
+                    .map(|(k, v)| (k.as_str(), to_edit_value(v)))
+                    .collect(),
+            ),
+            other => Item::Value(to_edit_value(other)),
+        };
+        sections.insert(key, item);
+    }
+    sections
+}
+
+fn to_edit_value(value: &toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => s.as_str().into(),
+        toml::Value::Integer(i) => (*i).into(),
+        toml::Value::Float(f) => (*f).into(),
+        toml::Value::Boolean(b) => (*b).into(),
+        toml::Value::Datetime(d) => d
+            .to_string()
+            .parse::<toml_edit::Datetime>()
+            .map(Value::from)
+            .unwrap_or_else(|_| d.to_string().into()),
+        toml::Value::Array(items) => Value::Array(items.iter().map(to_edit_value).collect()),
+        toml::Value::Table(table) => Value::InlineTable(
+            table
+                .iter()
+                .map(|(k, v)| (k.as_str(), to_edit_value(v)))
+                .collect(),
+        ),
+    }
+}
@@ -0,0 +1,135 @@
+use crate::results::{Answer, DayResult, DayStatus, PartResult, SavedResults};
+use crate::utils;
+
+/// Début de la section générée par `mush readme`
+pub const START_MARKER: &str = "<!-- mush:start -->";
+/// Fin de la section générée par `mush readme`
+pub const END_MARKER: &str = "<!-- mush:end -->";
+
+/// Génère le tableau de progression d'une année.
+///
+/// `days` liste les jours créés ; les jours ayant un résultat enregistré sont
+/// aussi affichés. Les réponses elles-mêmes ne sont pas publiées, seulement
+/// leur statut et leur temps. mush ne sait pas si une réponse est juste : le
+/// tableau compte les parties ayant affiché une réponse, hors `0` du template.
+pub fn render_year(saved: &SavedResults, days: &[u8]) -> String {
+    let mut days = days.to_vec();
+    days.extend(saved.days.iter().map(|result| result.day));
+    days.sort_unstable();
+    days.dedup();
+
+    let answered: usize = saved
+        .days
+        .iter()
+        .filter(|result| days.contains(&result.day))
+        .map(|result| {
+            [&result.part1, &result.part2]
+                .into_iter()
+                .filter(|part| is_answered(part))
+                .count()
+        })
+        .sum();
+
+    // Chaque jour garde le mode de sa dernière exécution : les deux peuvent cohabiter
+    let shown = || {
+        saved
+            .days
+            .iter()
+            .filter(|result| days.contains(&result.day))
+    };
+    let mixed = shown().any(|result| result.release) && shown().any(|result| !result.release);
+    let mode = match (mixed, shown().any(|result| result.release)) {
+        (true, _) => "modes release et debug (temps debug signalés)",
+        (false, true) => "mode release",
+        (false, false) => "mode debug",
+    };
+    let mut section = format!(
+        "### {}\n\n✅ {} partie(s) avec une réponse, non vérifiée(s) · temps mesurés en {}\n\n",
+        saved.year, answered, mode
+    );
+    section.push_str("| Jour | Partie 1 | Partie 2 | Temps P1 | Temps P2 |\n");
+    section.push_str("|:----:|:--------:|:--------:|---------:|---------:|\n");
+    for day in days {
+        let result = saved.get(day);
+        let source = utils::day_path(day, saved.year).join("src/main.rs");
+        section.push_str(&format!(
+            "| [{:02}]({}) | {} | {} | {} | {} |\n",
+            day,
+            source.display(),
+            part_status(result, 1),
+            part_status(result, 2),
+            part_time(result, 1, mixed),
+            part_time(result, 2, mixed),
+        ));
+    }
+    section
+}
+
+/// Remplace le contenu entre les marqueurs, ou ajoute la section en fin de fichier s'ils sont absents
+pub fn replace_section(content: &str, generated: &str) -> String {
+    let section = format!("{}\n{}{}", START_MARKER, generated, END_MARKER);
+    match (content.find(START_MARKER), content.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &content[..start],
+            section,
+            &content[end + END_MARKER.len()..]
+        ),
+        _ => {
+            let mut updated = content.to_string();
+            if !updated.is_empty() && !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            if !updated.is_empty() {
+                updated.push('\n');
+            }
+            updated.push_str(&section);
+            updated.push('\n');
+            updated
+        }
+    }
+}
+
+fn part_status(result: Option<&DayResult>, part: u8) -> &'static str {
+    let Some(result) = result else {
+        return "—";
+    };
+    let part_result = if part == 1 {
+        &result.part1
+    } else {
+        &result.part2
+    };
+    match (part_result, &result.status) {
+        (part_result, _) if is_answered(part_result) => "✅",
+        (PartResult::Answered { .. }, _) => "❔",
+        (PartResult::Skipped, _) => "—",
+        (PartResult::Missing, DayStatus::Failed { .. }) => "❌",
+        (PartResult::Missing, DayStatus::TimedOut { .. }) => "⏱️",
+        (PartResult::Missing, DayStatus::Ok) => "❔",
+    }
+}
+
+/// Partie ayant affiché une réponse autre que le `0` renvoyé par le template
+fn is_answered(part: &PartResult) -> bool {
+    matches!(part, PartResult::Answered { answer, .. } if *answer != Answer::Integer(0))
+}
+
+fn part_time(result: Option<&DayResult>, part: u8, mixed: bool) -> String {
+    let Some(result) = result else {
+        return String::new();
+    };
+    let part_result = if part == 1 {
+        &result.part1
+    } else {
+        &result.part2
+    };
+    match part_result {
+        PartResult::Answered {
+            time: Some(time), ..
+        } if mixed && !result.release => format!("{} (debug)", utils::format_duration(*time)),
+        PartResult::Answered {
+            time: Some(time), ..
+        } => utils::format_duration(*time),
+        _ => String::new(),
+    }
+}
@@ -1,40 +1,549 @@
+use anyhow::{Context, Result};
+use mush_helpers::ocr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::Duration;
+
+use crate::runner::ResourceUsage;
+
+/// Réponse affichée par une partie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Dessin sur plusieurs lignes, affiché sous `Part N:`, dont les lettres n'ont pas été reconnues
+    Art(Vec<String>),
+}
+
+impl Answer {
+    /// Réponse affichée sur la ligne `Part N: ...`.
+    ///
+    /// Seule l'écriture canonique d'un nombre donne un entier : `0124` ou `+5`
+    /// restent du texte, pour ne pas changer la réponse affichée.
+    pub fn parse(value: &str) -> Answer {
+        let value = value.trim();
+        match value.parse::<i128>() {
+            Ok(number) if number.to_string() == value => Answer::Integer(number),
+            _ => Answer::Text(value.to_string()),
+        }
+    }
+
+    /// Version sur une ligne, pour les affichages compacts
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Art(lines) => format!(
+                "dessin {}x{}",
+                lines
+                    .iter()
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or(0),
+                lines.len()
+            ),
+            answer => answer.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{}", number),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Art(lines) => f.write_str(&lines.join("\n")),
+        }
+    }
+}
+
+// Nombre JSON si possible, texte au-delà de u64 (serde_json ne relit pas les nombres plus grands)
+impl Serialize for Answer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(number) => match (i64::try_from(*number), u64::try_from(*number)) {
+                (Ok(number), _) => serializer.serialize_i64(number),
+                (_, Ok(number)) => serializer.serialize_u64(number),
+                _ => serializer.collect_str(number),
+            },
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Art(lines) => lines.serialize(serializer),
+        }
+    }
+}
+
+// Désérialisation manuelle : `#[serde(untagged)]` ne sait pas relire un i128.
+// Les réponses enregistrées en texte par les anciennes versions sont réinterprétées.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("un nombre, un texte ou une liste de lignes")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Answer, E> {
+                Ok(Answer::Integer(value.into()))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Answer, E> {
+                Ok(Answer::Integer(value.into()))
+            }
+
+            fn visit_i128<E>(self, value: i128) -> Result<Answer, E> {
+                Ok(Answer::Integer(value))
+            }
+
+            fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Answer, E> {
+                i128::try_from(value)
+                    .map(Answer::Integer)
+                    .map_err(|_| E::custom("réponse trop grande"))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Answer, E> {
+                Ok(Answer::parse(value))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Answer, A::Error> {
+                let mut lines = Vec::new();
+                while let Some(line) = seq.next_element()? {
+                    lines.push(line);
+                }
+                Ok(Answer::Art(lines))
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+/// Résultat d'une partie d'un jour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum PartResult {
+    /// La partie a affiché une réponse, avec son temps d'exécution (enregistré en ms)
+    Answered {
+        answer: Answer,
+        #[serde(default, with = "duration_ms")]
+        time: Option<Duration>,
+    },
+    /// La partie a été exécutée mais n'a affiché aucune réponse
+    Missing,
+    /// La partie n'a pas été exécutée (`--part`)
+    Skipped,
+}
+
+impl PartResult {
+    /// Extrait le résultat d'une partie (1 ou 2) de la sortie d'une solution.
+    ///
+    /// `selected` est la partie demandée avec `--part`, `None` pour les deux. Une
+    /// partie non demandée n'est `Skipped` que si elle est absente de la sortie : les
+    /// jours qui ignorent `AOC_PART` affichent quand même leurs deux réponses.
+    pub fn from_output(output: &str, part: u8, selected: Option<u8>) -> PartResult {
+        match parse_part(output, &format!("Part {}", part)) {
+            (Some(answer), time) => PartResult::Answered { answer, time },
+            (None, _) if selected.is_some_and(|selected| selected != part) => PartResult::Skipped,
+            (None, _) => PartResult::Missing,
+        }
+    }
+
+    /// Temps d'exécution, nul si la partie n'a pas de temps
+    pub fn time(&self) -> Duration {
+        match self {
+            PartResult::Answered { time, .. } => time.unwrap_or_default(),
+            PartResult::Missing | PartResult::Skipped => Duration::ZERO,
+        }
+    }
+}
+
+/// Nombre maximal de lignes d'erreur de compilation gardées dans l'extrait
+const COMPILE_EXCERPT_LINES: usize = 6;
+
+/// Cause de l'échec d'un jour
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Failure {
+    /// Le jour ne compile pas, avec un extrait de la première erreur
+    Compile { message: String },
+    /// La solution a paniqué
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+    /// La solution s'est terminée avec un code non nul
+    ExitCode { code: i32 },
+    /// La solution a été tuée par un signal (segfault, abort...)
+    Signal { signal: i32 },
+}
+
+impl Failure {
+    /// Extrait la première erreur de la sortie de `cargo build`
+    pub fn compile(stderr: &str) -> Failure {
+        let mut excerpt: Vec<&str> = stderr
+            .lines()
+            .skip_while(|line| !line.starts_with("error"))
+            .take_while(|line| !line.trim().is_empty())
+            .take(COMPILE_EXCERPT_LINES)
+            .collect();
+        if excerpt.is_empty() {
+            excerpt = last_lines(stderr, COMPILE_EXCERPT_LINES);
+        }
+        Failure::Compile {
+            message: excerpt.join("\n"),
+        }
+    }
+
+    /// Détermine la cause de l'échec d'une solution à partir de son statut et de sa sortie d'erreur
+    pub fn from_exit(status: Option<ExitStatus>, stderr: &str) -> Failure {
+        if let Some((message, location)) = parse_panic(stderr) {
+            return Failure::Panic { message, location };
+        }
+
+        #[cfg(unix)]
+        if let Some(signal) =
+            status.and_then(|status| std::os::unix::process::ExitStatusExt::signal(&status))
+        {
+            return Failure::Signal { signal };
+        }
+
+        Failure::ExitCode {
+            code: status.and_then(|status| status.code()).unwrap_or(-1),
+        }
+    }
+
+    /// Libellé court, pour le bilan
+    pub fn label(&self) -> String {
+        match self {
+            Failure::Compile { .. } => "compilation".to_string(),
+            Failure::Panic { .. } => "panique".to_string(),
+            Failure::ExitCode { code } => format!("code {}", code),
+            Failure::Signal { signal } => match signal_name(*signal) {
+                Some(name) => name.to_string(),
+                None => format!("signal {}", signal),
+            },
+        }
+    }
+
+    /// Description de l'échec, sur une ou plusieurs lignes
+    pub fn describe(&self) -> String {
+        match self {
+            Failure::Compile { message } => format!("Erreur de compilation\n{}", message),
+            Failure::Panic {
+                message,
+                location: Some(location),
+            } => format!("Panique à {} : {}", location, message),
+            Failure::Panic {
+                message,
+                location: None,
+            } => format!("Panique : {}", message),
+            Failure::ExitCode { code } => format!("Code de sortie {}", code),
+            Failure::Signal { signal } => match signal_name(*signal) {
+                Some(name) => format!("Tué par le signal {} ({})", signal, name),
+                None => format!("Tué par le signal {}", signal),
+            },
+        }
+    }
+}
+
+/// Extrait le message et l'emplacement d'une panique de la sortie d'erreur
+fn parse_panic(stderr: &str) -> Option<(String, Option<String>)> {
+    let lines: Vec<&str> = stderr.lines().collect();
+    let index = lines.iter().position(|line| line.contains("panicked at"))?;
+    let (_, rest) = lines[index].split_once("panicked at ")?;
+
+    // Format avant Rust 1.73 : panicked at 'message', src/main.rs:12:5
+    if let Some(old) = rest.strip_prefix('\'') {
+        return Some(match old.rsplit_once("', ") {
+            Some((message, location)) => (message.to_string(), Some(location.to_string())),
+            None => (old.trim_end_matches('\'').to_string(), None),
+        });
+    }
+
+    // Format actuel : panicked at src/main.rs:12:5:\nmessage
+    let message: Vec<&str> = lines[index + 1..]
+        .iter()
+        .copied()
+        .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace"))
+        .collect();
+    Some((
+        message.join("\n"),
+        Some(rest.trim_end_matches(':').to_string()),
+    ))
+}
+
+/// Nom usuel des signaux Unix courants
+fn signal_name(signal: i32) -> Option<&'static str> {
+    match signal {
+        4 => Some("SIGILL"),
+        6 => Some("SIGABRT"),
+        7 => Some("SIGBUS"),
+        8 => Some("SIGFPE"),
+        9 => Some("SIGKILL"),
+        11 => Some("SIGSEGV"),
+        15 => Some("SIGTERM"),
+        _ => None,
+    }
+}
+
+/// Dernières lignes non vides d'un texte
+pub fn last_lines(text: &str, count: usize) -> Vec<&str> {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+/// Issue de l'exécution d'un jour
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DayStatus {
+    /// La solution s'est terminée normalement
+    Ok,
+    /// La compilation ou l'exécution a échoué, avec la sortie d'erreur complète
+    Failed { failure: Failure, stderr: String },
+    /// La partie indiquée a dépassé le délai (`--timeout`)
+    TimedOut { part: u8 },
+}
+
 /// Structure pour stocker les résultats d'un jour
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DayResult {
     pub day: u8,
-    pub part1_result: Option<String>,
-    pub part1_time: Option<f64>,
-    pub part2_result: Option<String>,
-    pub part2_time: Option<f64>,
+    pub status: DayStatus,
+    pub part1: PartResult,
+    pub part2: PartResult,
+    /// Pic mémoire et temps CPU du processus, si disponibles
+    pub usage: Option<ResourceUsage>,
+    /// Durée d'exécution du processus mesurée par mush, hors compilation (enregistrée en ms)
+    #[serde(rename = "run_time_ms", default, with = "duration_ms")]
+    pub run_time: Option<Duration>,
+    /// Compilé en mode release
+    #[serde(default)]
+    pub release: bool,
 }
 
 impl DayResult {
-    pub fn total_time(&self) -> f64 {
-        self.part1_time.unwrap_or(0.0) + self.part2_time.unwrap_or(0.0)
+    /// Jour qui n'a pas pu être exécuté faute de compiler
+    pub fn compile_error(day: u8, release: bool, stderr: String) -> Self {
+        DayResult {
+            day,
+            status: DayStatus::Failed {
+                failure: Failure::compile(&stderr),
+                stderr,
+            },
+            part1: PartResult::Missing,
+            part2: PartResult::Missing,
+            usage: None,
+            run_time: None,
+            release,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == DayStatus::Ok
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.part1.time() + self.part2.time()
     }
 }
 
-/// Parse une partie (Part 1 ou Part 2) de la sortie
-pub fn parse_part(output: &str, part_name: &str) -> (Option<String>, Option<f64>) {
-    let mut result = None;
-    let mut time = None;
+/// Parse une partie (Part 1 ou Part 2) de la sortie.
+///
+/// La réponse suit `Part N:` sur la même ligne, ou sur les lignes suivantes pour
+/// un dessin, converti en texte si ses lettres sont reconnues. Le temps est lu sur la ligne `Time:` qui suit, avant la partie suivante.
+pub fn parse_part(output: &str, part_name: &str) -> (Option<Answer>, Option<Duration>) {
+    let prefix = format!("{}:", part_name);
+    let mut lines = output.lines().peekable();
 
-    for line in output.lines() {
-        if line.starts_with(part_name) {
-            // Format: "Part 1: 12345"
-            if let Some(value) = line.split(':').nth(1) {
-                result = Some(value.trim().to_string());
+    while let Some(line) = lines.next() {
+        // Format: "Part 1: 12345"
+        let Some(value) = line.strip_prefix(&prefix) else {
+            continue;
+        };
+        let answer = if value.trim().is_empty() {
+            // Dessin : toutes les lignes jusqu'à "Time:" ou la partie suivante
+            let mut art: Vec<String> = Vec::new();
+            while let Some(line) =
+                lines.next_if(|line| !line.starts_with("Time:") && !line.starts_with("Part "))
+            {
+                art.push(line.trim_end().to_string());
+            }
+            while art.last().is_some_and(|line| line.is_empty()) {
+                art.pop();
             }
-        } else if line.starts_with("Time:") && result.is_some() && time.is_none() {
-            // Format: "Time: 0.1234ms"
-            if let Some(time_str) = line.split(':').nth(1) {
-                let time_str = time_str.trim().trim_end_matches("ms");
-                if let Ok(t) = time_str.parse::<f64>() {
-                    time = Some(t);
+            let blank = art.iter().take_while(|line| line.is_empty()).count();
+            art.drain(..blank);
+            if art.is_empty() {
+                Answer::Text(String::new())
+            } else if let Some(letters) = ocr::parse(&art.join("\n")) {
+                // Lettres reconnues : la réponse à soumettre
+                Answer::Text(letters)
+            } else {
+                Answer::Art(art)
+            }
+        } else {
+            Answer::parse(value)
+        };
+
+        // Format: "Time: 1.234567ms" (Debug de Duration), "Time: 850ns"...
+        let time = lines
+            .take_while(|line| !line.starts_with("Part "))
+            .find_map(|line| line.strip_prefix("Time:"))
+            .and_then(parse_time);
+        return (Some(answer), time);
+    }
+
+    (None, None)
+}
+
+/// Parse un temps affiché par une solution : `850ns`, `12.5µs`, `12.5us`, `1.2ms`, `1.5s`.
+///
+/// Un nombre sans unité est interprété en millisecondes.
+pub fn parse_time(raw: &str) -> Option<Duration> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(raw.len());
+    let (value, unit) = raw.split_at(split);
+    let value: f64 = value.parse().ok()?;
+    let seconds = match unit.trim() {
+        "ns" => value / 1e9,
+        // Signe micro (Debug de Duration) ou lettre grecque mu
+        "µs" | "μs" | "us" => value / 1e6,
+        "" | "ms" => value / 1e3,
+        "s" => value,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Enregistre les temps en ms décimales, comme les fichiers de résultats existants
+mod duration_ms {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_some(&(time.as_secs_f64() * 1000.0)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        let time: Option<f64> = Option::deserialize(deserializer)?;
+        Ok(time.and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok()))
+    }
+}
+
+/// Répertoire des derniers résultats enregistrés, ignoré par git avec `target/`
+const RESULTS_DIR: &str = "target/mush";
+
+/// Derniers résultats connus des jours d'une année, enregistrés par `run-all` et `mush tui`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SavedResults {
+    pub year: u16,
+    /// Dernier résultat de chaque jour exécuté, trié par jour
+    pub days: Vec<DayResult>,
+}
+
+impl SavedResults {
+    /// Chemin du fichier de résultats d'une année
+    pub fn path(year: u16) -> PathBuf {
+        PathBuf::from(RESULTS_DIR).join(format!("results-{}.json", year))
+    }
+
+    /// Charge les résultats enregistrés d'une année, vides si aucun n'a été enregistré.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier existe mais est illisible ou invalide.
+    pub fn load(year: u16) -> Result<SavedResults> {
+        let path = Self::path(year);
+        if !path.exists() {
+            return Ok(SavedResults {
+                year,
+                ..SavedResults::default()
+            });
+        }
+        Self::read(&path)
+    }
+
+    /// Lit un fichier de résultats (`results-YYYY.json` ou `run-all --json`).
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le fichier est illisible ou invalide.
+    pub fn read(path: &Path) -> Result<SavedResults> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Impossible de lire le fichier {:?}", path))?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Le fichier de résultats {:?} est invalide", path))?;
+
+        // Les anciennes versions enregistraient un seul mode pour toute l'année
+        if let Some(release) = value.get("release").cloned() {
+            for day in value["days"].as_array_mut().into_iter().flatten() {
+                if let Some(day) = day.as_object_mut() {
+                    day.entry("release").or_insert(release.clone());
                 }
             }
         }
+
+        serde_json::from_value(value)
+            .with_context(|| format!("Le fichier de résultats {:?} est invalide", path))
     }
 
-    (result, time)
+    /// Remplace les résultats des jours exécutés, en gardant ceux des autres jours.
+    ///
+    /// Une partie non exécutée (`--part`) garde son résultat précédent s'il a été
+    /// mesuré dans le même mode de compilation.
+    pub fn merge(&mut self, results: &[DayResult]) {
+        for result in results {
+            let mut result = result.clone();
+            if let Some(previous) = self.get(result.day).filter(|p| p.release == result.release) {
+                if result.part1 == PartResult::Skipped {
+                    result.part1 = previous.part1.clone();
+                }
+                if result.part2 == PartResult::Skipped {
+                    result.part2 = previous.part2.clone();
+                }
+            }
+            self.days.retain(|saved| saved.day != result.day);
+            self.days.push(result);
+        }
+        self.days.sort_by_key(|result| result.day);
+    }
+
+    /// Dernier résultat enregistré d'un jour
+    pub fn get(&self, day: u8) -> Option<&DayResult> {
+        self.days.iter().find(|result| result.day == day)
+    }
+
+    /// Enregistre les résultats.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'écriture du fichier échoue.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path(self.year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Impossible de créer le répertoire {:?}", parent))?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Impossible d'écrire dans le fichier {:?}", path))
+    }
 }
@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Intervalle de vérification de la fin du processus
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Ressources consommées par un processus terminé (Linux uniquement)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Pic de mémoire résidente, en kilo-octets
+    pub max_rss_kb: u64,
+    /// Temps CPU passé en mode utilisateur
+    pub user_time_ms: f64,
+    /// Temps CPU passé en mode noyau
+    pub system_time_ms: f64,
+}
+
+impl ResourceUsage {
+    /// Pic de mémoire lisible (ex: `12.3 Mo`)
+    pub fn memory(&self) -> String {
+        let kb = self.max_rss_kb as f64;
+        if kb >= 1024.0 * 1024.0 {
+            format!("{:.2} Go", kb / (1024.0 * 1024.0))
+        } else if kb >= 1024.0 {
+            format!("{:.1} Mo", kb / 1024.0)
+        } else {
+            format!("{} Ko", self.max_rss_kb)
+        }
+    }
+}
+
+/// Sortie d'un processus lancé par [`run`]
+#[derive(Debug)]
+pub struct ProcessOutput {
+    /// Statut de sortie, `None` si le processus a été tué après un timeout
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+    /// Ressources consommées, y compris si le processus a été tué
+    pub usage: Option<ResourceUsage>,
+    /// Durée mesurée du lancement à la fin (ou à l'arrêt) du processus
+    pub elapsed: Duration,
+    /// Vrai si le processus a dépassé le délai et a été tué
+    pub timed_out: bool,
+}
+
+/// Lance une commande en capturant ses sorties, avec un délai optionnel par partie.
+///
+/// Le délai repart de zéro à chaque ligne `Part N: ...` affichée par la
+/// solution : chaque partie dispose donc de `timeout` pour se terminer. À
+/// l'expiration, le processus et tous ses descendants sont tués.
+///
+/// # Errors
+///
+/// Retourne une erreur si le processus ne peut pas être lancé ou attendu.
+pub fn run(command: &mut Command, timeout: Option<Duration>) -> Result<ProcessOutput> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Impossible de lancer {:?}", command.get_program()))?;
+
+    let stdout = child.stdout.take().expect("stdout capturé");
+    let mut stderr = child.stderr.take().expect("stderr capturé");
+
+    // Les lignes de stdout sont transmises au fil de l'eau pour suivre les parties
+    let (sender, receiver) = mpsc::channel();
+    let stdout_reader = thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let stderr_reader = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stderr.read_to_end(&mut buffer);
+        String::from_utf8_lossy(&buffer).into_owned()
+    });
+
+    let mut deadline = timeout.map(|t| Instant::now() + t);
+    let mut lines = Vec::new();
+    let mut timed_out = false;
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(line) => {
+                if line.starts_with("Part ") {
+                    deadline = timeout.map(|t| Instant::now() + t);
+                }
+                lines.push(line);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                timed_out = true;
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // stdout fermé : le processus se termine, dans la limite du délai restant
+    let exit = if timed_out {
+        None
+    } else {
+        wait_until(&mut child, deadline)?
+    };
+    let (status, usage) = match exit {
+        Some((status, usage)) => (Some(status), usage),
+        None => {
+            timed_out = true;
+            kill_tree(&mut child);
+            (None, wait(&mut child)?.1)
+        }
+    };
+
+    let elapsed = start.elapsed();
+    let _ = stdout_reader.join();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    Ok(ProcessOutput {
+        status,
+        stdout: lines.join("\n"),
+        stderr,
+        usage,
+        elapsed,
+        timed_out,
+    })
+}
+
+/// Lance une commande avec les entrées/sorties du terminal et attend sa fin.
+///
+/// # Errors
+///
+/// Retourne une erreur si le processus ne peut pas être lancé ou attendu.
+pub fn run_inherited(command: &mut Command) -> Result<(ExitStatus, Option<ResourceUsage>)> {
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("Impossible de lancer {:?}", command.get_program()))?;
+    wait(&mut child)
+}
+
+/// Attend la fin du processus, ou retourne `None` si le délai expire avant
+fn wait_until(
+    child: &mut Child,
+    deadline: Option<Instant>,
+) -> Result<Option<(ExitStatus, Option<ResourceUsage>)>> {
+    let Some(deadline) = deadline else {
+        return wait(child).map(Some);
+    };
+
+    loop {
+        if let Some(exit) = try_wait(child)? {
+            return Ok(Some(exit));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Attend la fin du processus et relève ses ressources consommées
+fn wait(child: &mut Child) -> Result<(ExitStatus, Option<ResourceUsage>)> {
+    #[cfg(target_os = "linux")]
+    let exit = linux::wait4(child.id(), false)
+        .map(|exit| exit.map(|(status, usage)| (status, Some(usage))));
+    #[cfg(not(target_os = "linux"))]
+    let exit = child.wait().map(|status| Some((status, None)));
+
+    exit.context("Impossible d'attendre la fin du processus")?
+        .context("Le processus ne s'est pas terminé")
+}
+
+/// Comme [`wait`], sans bloquer si le processus tourne encore
+fn try_wait(child: &mut Child) -> Result<Option<(ExitStatus, Option<ResourceUsage>)>> {
+    #[cfg(target_os = "linux")]
+    let exit = linux::wait4(child.id(), true)
+        .map(|exit| exit.map(|(status, usage)| (status, Some(usage))));
+    #[cfg(not(target_os = "linux"))]
+    let exit = child
+        .try_wait()
+        .map(|status| status.map(|status| (status, None)));
+
+    exit.context("Impossible d'attendre la fin du processus")
+}
+
+/// Tue un processus et tous ses descendants (`cargo run` lance la solution dans un sous-processus)
+fn kill_tree(child: &mut Child) {
+    #[cfg(target_os = "linux")]
+    let descendants = linux::descendants(child.id());
+
+    let _ = child.kill();
+
+    #[cfg(target_os = "linux")]
+    for pid in descendants {
+        // SAFETY: kill n'accède à aucune mémoire, au pire le processus n'existe plus
+        unsafe {
+            libc::kill(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use super::ResourceUsage;
+
+    /// Attend un processus fils avec `wait4` pour relever ses ressources consommées.
+    ///
+    /// Avec `nohang`, retourne `None` si le processus tourne encore. Le processus
+    /// est réclamé : `Child::wait` ne doit plus être appelé ensuite.
+    pub fn wait4(pid: u32, nohang: bool) -> io::Result<Option<(ExitStatus, ResourceUsage)>> {
+        let flags = if nohang { libc::WNOHANG } else { 0 };
+        let mut status = 0;
+        // SAFETY: rusage est une structure C sans invariant, entièrement écrite par wait4
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+        loop {
+            // SAFETY: les pointeurs désignent des variables locales valides
+            let result =
+                unsafe { libc::wait4(pid as libc::pid_t, &mut status, flags, &mut rusage) };
+            match result {
+                0 => return Ok(None),
+                -1 => {
+                    let error = io::Error::last_os_error();
+                    if error.kind() != io::ErrorKind::Interrupted {
+                        return Err(error);
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let usage = ResourceUsage {
+            // ru_maxrss est exprimé en kilo-octets sous Linux
+            max_rss_kb: rusage.ru_maxrss.max(0) as u64,
+            user_time_ms: timeval_ms(rusage.ru_utime),
+            system_time_ms: timeval_ms(rusage.ru_stime),
+        };
+        Ok(Some((ExitStatus::from_raw(status), usage)))
+    }
+
+    fn timeval_ms(time: libc::timeval) -> f64 {
+        time.tv_sec as f64 * 1000.0 + time.tv_usec as f64 / 1000.0
+    }
+
+    /// Descendants d'un processus, d'après le PPID de chaque entrée de `/proc`
+    pub fn descendants(root: u32) -> Vec<u32> {
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                continue;
+            };
+            // Format : "pid (comm) state ppid ...", comm pouvant contenir des espaces
+            let Some(ppid) = fs::read_to_string(entry.path().join("stat"))
+                .ok()
+                .and_then(|stat| {
+                    let (_, rest) = stat.rsplit_once(')')?;
+                    rest.split_whitespace().nth(1)?.parse::<u32>().ok()
+                })
+            else {
+                continue;
+            };
+            children.entry(ppid).or_default().push(pid);
+        }
+
+        let mut result = Vec::new();
+        let mut stack = vec![root];
+        while let Some(pid) = stack.pop() {
+            for &child in children.get(&pid).into_iter().flatten() {
+                result.push(child);
+                stack.push(child);
+            }
+        }
+        result
+    }
+}
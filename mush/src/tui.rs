@@ -0,0 +1,433 @@
+use anyhow::{Context, Result};
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::{self, BufRead, Write};
+use std::process::Command as ShellCommand;
+
+use crate::calendar::Event;
+use crate::commands::{self, RunAllOptions};
+use crate::inputs;
+use crate::libtest::TestReport;
+use crate::results::{DayResult, DayStatus, PartResult, SavedResults};
+use crate::utils;
+
+/// Hauteur d'une case de la grille (bordures comprises)
+const CELL_HEIGHT: u16 = 5;
+
+/// État d'un jour affiché dans une case
+#[derive(Debug, Clone)]
+pub struct DayCell {
+    pub day: u8,
+    pub scaffolded: bool,
+    pub has_input: bool,
+    /// Dernier résultat enregistré par `run-all` ou le tableau de bord
+    pub result: Option<DayResult>,
+}
+
+/// État du tableau de bord, indépendant du terminal
+pub struct App {
+    pub year: u16,
+    pub release: bool,
+    pub cells: Vec<DayCell>,
+    pub selected: usize,
+    pub columns: usize,
+    /// Message affiché en bas de l'écran après une action
+    pub message: String,
+}
+
+/// Action demandée au clavier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Scaffold,
+    Run,
+    Test,
+    Open,
+    Quit,
+}
+
+impl App {
+    /// Lit l'état des jours d'une année depuis le disque, sans accès réseau.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si l'année est invalide ou si les résultats enregistrés sont illisibles.
+    pub fn load(year: u16, release: bool) -> Result<App> {
+        let event = Event::for_year(year)?;
+        let saved = SavedResults::load(year)?;
+        let cells = (1..=event.days)
+            .map(|day| {
+                let mut cell = DayCell {
+                    day,
+                    scaffolded: false,
+                    has_input: false,
+                    result: saved.get(day).cloned(),
+                };
+                cell.refresh(year);
+                cell
+            })
+            .collect();
+
+        Ok(App {
+            year,
+            release,
+            cells,
+            selected: 0,
+            // 5x5 pour 25 jours, 4x3 pour 12
+            columns: if event.days > 12 { 5 } else { 4 },
+            message: String::new(),
+        })
+    }
+
+    pub fn selected_cell(&self) -> &DayCell {
+        &self.cells[self.selected]
+    }
+
+    /// Déplace la sélection dans la grille, sans en sortir
+    pub fn move_selection(&mut self, columns: isize, rows: isize) {
+        let target = self.selected as isize + columns + rows * self.columns as isize;
+        if (0..self.cells.len() as isize).contains(&target) {
+            self.selected = target as usize;
+        }
+    }
+
+    /// Action associée à une touche, les déplacements étant appliqués directement
+    pub fn handle_key(&mut self, key: KeyCode) -> Option<Action> {
+        match key {
+            KeyCode::Left | KeyCode::Char('h') => self.move_selection(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_selection(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(0, 1),
+            KeyCode::Char('s') => return Some(Action::Scaffold),
+            KeyCode::Char('r') | KeyCode::Enter => return Some(Action::Run),
+            KeyCode::Char('t') => return Some(Action::Test),
+            KeyCode::Char('o') => return Some(Action::Open),
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            _ => {}
+        }
+        None
+    }
+}
+
+impl DayCell {
+    /// Relit l'état du jour sur le disque
+    fn refresh(&mut self, year: u16) {
+        let path = utils::day_path(self.day, year);
+        self.scaffolded = path.join("Cargo.toml").exists();
+        self.has_input = std::fs::metadata(path.join("input.txt")).is_ok_and(|m| m.len() > 0);
+    }
+}
+
+/// Lance le tableau de bord d'une année.
+///
+/// # Errors
+///
+/// Retourne une erreur si le terminal ne peut pas être initialisé ou si une action échoue.
+pub fn run(year: u16, release: bool) -> Result<()> {
+    let mut app = App::load(year, release)?;
+    let mut terminal = ratatui::try_init().context("Impossible d'initialiser le terminal")?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        let TermEvent::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let Some(action) = app.handle_key(key.code) else {
+            continue;
+        };
+
+        let day = app.selected_cell().day;
+        match action {
+            Action::Quit => return Ok(()),
+            Action::Run => {
+                app.message = format!("⏳ Exécution du jour {}...", day);
+                terminal.draw(|frame| draw(frame, app))?;
+                if let Err(e) = run_day(app, day) {
+                    app.message = format!("❌ {:#}", e);
+                }
+            }
+            Action::Test => {
+                app.message = format!("⏳ Tests du jour {}...", day);
+                terminal.draw(|frame| draw(frame, app))?;
+                app.message = test_day(app, day).unwrap_or_else(|e| format!("❌ {:#}", e));
+            }
+            Action::Scaffold => {
+                // La création affiche sa progression et peut télécharger l'input
+                let year = app.year;
+                suspend(terminal, || {
+                    println!("🎄 Préparation du jour {} de l'année {}...", day, year);
+                    if let Err(e) = commands::create_scaffold(day, year) {
+                        println!("❌ {:#}", e);
+                    }
+                    wait_for_enter()
+                })?;
+                app.message = format!("Jour {} préparé", day);
+            }
+            Action::Open => {
+                let main = utils::day_path(day, app.year).join("src/main.rs");
+                if !main.exists() {
+                    app.message = format!("Le jour {} n'existe pas : `s` pour le créer", day);
+                    continue;
+                }
+                let editor = std::env::var("VISUAL")
+                    .or_else(|_| std::env::var("EDITOR"))
+                    .unwrap_or_else(|_| "vi".to_string());
+                suspend(terminal, || {
+                    ShellCommand::new(&editor)
+                        .arg(&main)
+                        .status()
+                        .with_context(|| format!("Impossible de lancer l'éditeur {}", editor))
+                        .map(|_| ())
+                })?;
+                app.message.clear();
+            }
+        }
+        let year = app.year;
+        app.cells[app.selected].refresh(year);
+    }
+}
+
+/// Quitte temporairement le tableau de bord pour une commande interactive
+fn suspend(terminal: &mut DefaultTerminal, action: impl FnOnce() -> Result<()>) -> Result<()> {
+    ratatui::restore();
+    let result = action();
+    *terminal = ratatui::try_init().context("Impossible de réinitialiser le terminal")?;
+    result
+}
+
+fn wait_for_enter() -> Result<()> {
+    print!("\nAppuyez sur Entrée pour revenir au tableau de bord");
+    io::stdout().flush()?;
+    io::stdin().lock().read_line(&mut String::new())?;
+    Ok(())
+}
+
+/// Exécute un jour et enregistre son résultat
+fn run_day(app: &mut App, day: u8) -> Result<()> {
+    if !app.selected_cell().scaffolded {
+        app.message = format!("Le jour {} n'existe pas : `s` pour le créer", day);
+        return Ok(());
+    }
+
+    let decrypted = inputs::ensure_plaintext(&utils::day_path(day, app.year))?;
+    let options = RunAllOptions {
+        release: app.release,
+        ..RunAllOptions::default()
+    };
+    let (results, _) = commands::collect_results(app.year, &[day], options, |_| {})?;
+    let mut saved = SavedResults::load(app.year)?;
+    saved.merge(&results);
+    saved.save()?;
+
+    let result = results.into_iter().next();
+    app.message = match result.as_ref().map(|r| &r.status) {
+        Some(DayStatus::Ok) => format!("✅ Jour {} exécuté", day),
+        Some(DayStatus::Failed { failure, .. }) => {
+            format!("❌ Jour {} : {}", day, failure.label())
+        }
+        Some(DayStatus::TimedOut { part }) => format!("⏱️  Jour {} : partie {}", day, part),
+        None => String::new(),
+    };
+    if decrypted {
+        app.message = format!("🔓 input.txt déchiffré — {}", app.message);
+    }
+    app.cells[app.selected].result = result;
+    Ok(())
+}
+
+/// Lance les tests d'un jour et retourne le message à afficher
+fn test_day(app: &App, day: u8) -> Result<String> {
+    if !app.selected_cell().scaffolded {
+        return Ok(format!("Le jour {} n'existe pas : `s` pour le créer", day));
+    }
+
+    let output = ShellCommand::new("cargo")
+        .arg("test")
+        .arg("-p")
+        .arg(utils::package_name(day, app.year))
+        .output()
+        .context("Échec de l'exécution de la commande cargo test")?;
+    Ok(
+        match TestReport::parse(&String::from_utf8_lossy(&output.stdout)) {
+            None => format!("❌ Jour {} : les tests ne compilent pas", day),
+            Some(report) if report.failed == 0 => {
+                format!("🧪 Jour {} : ✅ {}/{}", day, report.passed, report.total())
+            }
+            Some(report) => format!(
+                "🧪 Jour {} : ❌ {}/{} réussis ({})",
+                day,
+                report.passed,
+                report.total(),
+                report
+                    .failures
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+    )
+}
+
+/// Dessine le tableau de bord complet
+pub fn draw(frame: &mut Frame, app: &App) {
+    let rows = app.cells.len().div_ceil(app.columns);
+    let [title, grid, details, help] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(rows as u16 * CELL_HEIGHT),
+        Constraint::Min(6),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let mode = if app.release { " (release)" } else { "" };
+    frame.render_widget(
+        Paragraph::new(format!("🎄 Advent of Code {}{}", app.year, mode))
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+        title,
+    );
+
+    let row_areas = Layout::vertical(vec![Constraint::Length(CELL_HEIGHT); rows]).split(grid);
+    for (index, cell) in app.cells.iter().enumerate() {
+        let row = Layout::horizontal(vec![Constraint::Ratio(1, app.columns as u32); app.columns])
+            .split(row_areas[index / app.columns]);
+        draw_cell(frame, row[index % app.columns], cell, index == app.selected);
+    }
+
+    draw_details(frame, details, app);
+    frame.render_widget(
+        Paragraph::new(
+            "←↑↓→/hjkl déplacer · s créer · r exécuter · t tester · o ouvrir · q quitter",
+        )
+        .style(Style::new().fg(Color::DarkGray)),
+        help,
+    );
+}
+
+fn draw_cell(frame: &mut Frame, area: Rect, cell: &DayCell, selected: bool) {
+    let (icon, color) = match cell.result.as_ref().map(|r| &r.status) {
+        _ if !cell.scaffolded => ("·", Color::DarkGray),
+        Some(DayStatus::Ok) => ("✅", Color::Green),
+        Some(DayStatus::Failed { .. }) => ("❌", Color::Red),
+        Some(DayStatus::TimedOut { .. }) => ("⏱️", Color::Yellow),
+        None => ("○", Color::Gray),
+    };
+    let mut border = Style::new().fg(color);
+    if selected {
+        border = border.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+    }
+
+    let mut status = vec![Span::raw(if cell.has_input { "📥 " } else { "   " })];
+    if let Some(result) = cell.result.as_ref().filter(|r| r.is_ok()) {
+        status.push(Span::raw(utils::format_duration(result.total_time())));
+    }
+    let lines = vec![
+        Line::from(status),
+        part_line(1, cell.result.as_ref().map(|r| &r.part1)),
+        part_line(2, cell.result.as_ref().map(|r| &r.part2)),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border)
+        .title(format!(" {} Day {:02} ", icon, cell.day));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn part_line(part: u8, result: Option<&PartResult>) -> Line<'static> {
+    match result {
+        Some(PartResult::Answered { answer, .. }) => {
+            Line::from(format!("P{} {}", part, answer.summary()))
+        }
+        _ => Line::from(Span::styled(
+            format!("P{} -", part),
+            Style::new().fg(Color::DarkGray),
+        )),
+    }
+}
+
+fn draw_details(frame: &mut Frame, area: Rect, app: &App) {
+    let cell = app.selected_cell();
+    let mut lines = vec![Line::from(format!(
+        "{} · {}",
+        utils::day_path(cell.day, app.year).display(),
+        match (cell.scaffolded, cell.has_input) {
+            (false, _) => "non créé",
+            (true, false) => "input absent",
+            (true, true) => "input présent",
+        }
+    ))];
+
+    match &cell.result {
+        None => lines.push(Line::from("Aucune exécution enregistrée")),
+        Some(result) => {
+            for (part, part_result) in [(1, &result.part1), (2, &result.part2)] {
+                lines.push(Line::from(match part_result {
+                    PartResult::Answered { answer, time } => match time {
+                        Some(time) => format!(
+                            "Part {}: {} ({})",
+                            part,
+                            answer.summary(),
+                            utils::format_duration(*time)
+                        ),
+                        None => format!("Part {}: {}", part, answer.summary()),
+                    },
+                    PartResult::Missing => format!("Part {}: aucune réponse", part),
+                    PartResult::Skipped => format!("Part {}: non exécutée", part),
+                }));
+            }
+            if let Some(usage) = result.usage {
+                lines.push(Line::from(format!(
+                    "Mémoire: {} | CPU: {:.1}ms user, {:.1}ms sys",
+                    usage.memory(),
+                    usage.user_time_ms,
+                    usage.system_time_ms
+                )));
+            }
+            match &result.status {
+                DayStatus::Failed { failure, .. } => {
+                    lines.extend(
+                        failure
+                            .describe()
+                            .lines()
+                            .map(|l| Line::from(l.to_string())),
+                    );
+                }
+                DayStatus::TimedOut { part } => {
+                    lines.push(Line::from(format!("Partie {} interrompue (timeout)", part)));
+                }
+                DayStatus::Ok => {}
+            }
+        }
+    }
+
+    if !app.message.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            app.message.clone(),
+            Style::new().add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Day {:02} ", cell.day));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
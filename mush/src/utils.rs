@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::io::Write;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 /// Crée un fichier avec le contenu spécifié si celui-ci n'existe pas déjà.
 ///
@@ -32,3 +32,190 @@ pub fn create_file(path: &Path, content: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Nom du package Cargo d'un jour : `day{XX}-{year}`
+pub fn package_name(day: u8, year: u16) -> String {
+    format!("day{:02}-{}", day, year)
+}
+
+/// Répertoire d'un jour dans le workspace : `solutions/{year}/day{XX}`
+pub fn day_path(day: u8, year: u16) -> PathBuf {
+    PathBuf::from("solutions")
+        .join(year.to_string())
+        .join(format!("day{:02}", day))
+}
+
+/// Liste les jours présents dans `solutions/`, triés par année puis par jour.
+///
+/// Seuls les répertoires de la forme `solutions/{year}/day{XX}` sont retenus.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture d'un répertoire échoue.
+pub fn scaffolded_days() -> Result<Vec<(u16, u8, PathBuf)>> {
+    day_dirs(Path::new("solutions"))
+}
+
+/// Liste les répertoires `{year}/day{XX}` d'un répertoire, triés par année puis par jour.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture d'un répertoire échoue.
+pub fn day_dirs(root: &Path) -> Result<Vec<(u16, u8, PathBuf)>> {
+    let mut days = Vec::new();
+    if !root.is_dir() {
+        return Ok(days);
+    }
+
+    for year_entry in
+        fs::read_dir(root).with_context(|| format!("Impossible de lire {:?}", root))?
+    {
+        let year_entry = year_entry?;
+        let Some(year) = year_entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u16>().ok())
+        else {
+            continue;
+        };
+        if !year_entry.path().is_dir() {
+            continue;
+        }
+
+        for day_entry in fs::read_dir(year_entry.path())? {
+            let day_entry = day_entry?;
+            let Some(day) = day_entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("day"))
+                .and_then(|num| num.parse::<u8>().ok())
+            else {
+                continue;
+            };
+            if day_entry.path().is_dir() {
+                days.push((year, day, day_entry.path()));
+            }
+        }
+    }
+
+    days.sort();
+    Ok(days)
+}
+
+/// Demande une confirmation oui/non sur l'entrée standard (non par défaut).
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture de l'entrée standard échoue.
+pub fn confirm(question: &str) -> Result<bool> {
+    print!("{} [o/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("Impossible de lire la réponse")?;
+    Ok(matches!(
+        answer.trim().to_lowercase().as_str(),
+        "o" | "oui" | "y" | "yes"
+    ))
+}
+
+/// Pose une question sur l'entrée standard et retourne la réponse, sans espaces autour.
+///
+/// # Errors
+///
+/// Retourne une erreur si la lecture de l'entrée standard échoue.
+pub fn prompt(question: &str) -> Result<String> {
+    print!("{} ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("Impossible de lire la réponse")?;
+    Ok(answer.trim().to_string())
+}
+
+/// Différence ligne à ligne entre deux textes, au format `+ ajout` / `- retrait`.
+///
+/// Seules les lignes modifiées sont retournées.
+pub fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Plus longue sous-séquence commune, calculée depuis la fin
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(format!("+ {}", new[j]));
+            j += 1;
+        } else {
+            diff.push(format!("- {}", old[i]));
+            i += 1;
+        }
+    }
+    diff
+}
+
+/// Parse une durée saisie en ligne de commande : `500ms`, `30s`, `2m`, `1h`.
+///
+/// Un nombre sans unité est interprété en secondes.
+///
+/// # Errors
+///
+/// Retourne un message d'erreur si la durée ou son unité est invalide.
+pub fn parse_duration(raw: &str) -> std::result::Result<std::time::Duration, String> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(raw.len());
+    let (value, unit) = raw.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("Durée invalide : {:?}", raw))?;
+
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" | "min" => value * 60.0,
+        "h" => value * 3600.0,
+        other => {
+            return Err(format!(
+                "Unité de durée inconnue : {:?} (ms, s, m ou h)",
+                other
+            ))
+        }
+    };
+    std::time::Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("Durée invalide : {:?}", raw))
+}
+
+/// Affiche une durée dans l'unité la plus lisible : `850 ns`, `12.35 µs`, `4.20 ms`, `1.50 s`
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
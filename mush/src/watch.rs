@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Délai de regroupement des événements : un enregistrement en produit souvent plusieurs
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Fichiers du jour, hors `src/` et `examples/`, dont la modification relance les tests
+const WATCHED_FILES: &[&str] = &["examples.toml", "example.txt", "input.txt"];
+
+/// Répertoires du jour surveillés récursivement
+const WATCHED_DIRS: &[&str] = &["src", "examples"];
+
+/// Surveille le `src/`, les exemples et le `input.txt` d'un jour (inotify sous Linux)
+pub struct DayWatcher {
+    day_path: PathBuf,
+    receiver: Receiver<notify::Result<Event>>,
+    // Gardé pour que la surveillance reste active
+    _watcher: RecommendedWatcher,
+}
+
+impl DayWatcher {
+    /// Démarre la surveillance du répertoire d'un jour.
+    ///
+    /// Le répertoire du jour est surveillé plutôt que les fichiers eux-mêmes :
+    /// les éditeurs qui enregistrent en remplaçant le fichier restent détectés.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si le répertoire n'existe pas ou ne peut pas être surveillé.
+    pub fn new(day_path: &Path) -> Result<Self> {
+        let day_path = day_path
+            .canonicalize()
+            .with_context(|| format!("Le répertoire {:?} est introuvable", day_path))?;
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .context("Impossible de démarrer la surveillance des fichiers")?;
+        watcher
+            .watch(&day_path, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Impossible de surveiller {:?}", day_path))?;
+        for dir in WATCHED_DIRS {
+            let dir = day_path.join(dir);
+            if dir.is_dir() {
+                watcher
+                    .watch(&dir, RecursiveMode::Recursive)
+                    .with_context(|| format!("Impossible de surveiller {:?}", dir))?;
+            }
+        }
+
+        Ok(DayWatcher {
+            day_path,
+            receiver,
+            _watcher: watcher,
+        })
+    }
+
+    /// Attend une modification et retourne les fichiers concernés.
+    ///
+    /// Les événements reçus pendant [`DEBOUNCE`] après le premier sont regroupés.
+    /// Retourne `None` si `timeout` expire sans modification.
+    ///
+    /// # Errors
+    ///
+    /// Retourne une erreur si la surveillance s'interrompt.
+    pub fn next_change(&self, timeout: Option<Duration>) -> Result<Option<Vec<PathBuf>>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut changed = Vec::new();
+
+        // Attendre un premier événement pertinent
+        while changed.is_empty() {
+            let event = match deadline {
+                Some(deadline) => {
+                    match self
+                        .receiver
+                        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(event) => event,
+                        Err(RecvTimeoutError::Timeout) => return Ok(None),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            return Err(anyhow::anyhow!(
+                                "La surveillance des fichiers s'est arrêtée"
+                            ))
+                        }
+                    }
+                }
+                None => self
+                    .receiver
+                    .recv()
+                    .context("La surveillance des fichiers s'est arrêtée")?,
+            };
+            self.collect(event, &mut changed)?;
+        }
+
+        // Regrouper les événements qui suivent
+        while let Ok(event) = self.receiver.recv_timeout(DEBOUNCE) {
+            self.collect(event, &mut changed)?;
+        }
+
+        Ok(Some(changed))
+    }
+
+    fn collect(&self, event: notify::Result<Event>, changed: &mut Vec<PathBuf>) -> Result<()> {
+        let event = event.context("Erreur de surveillance des fichiers")?;
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(());
+        }
+        for path in event.paths {
+            if self.is_relevant(&path) && !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.day_path) else {
+            return false;
+        };
+        // Le fichier de tests généré est réécrit par `watch` lui-même
+        if relative == Path::new(crate::examples::TESTS_FILE) {
+            return false;
+        }
+        WATCHED_DIRS
+            .iter()
+            .any(|dir| relative.starts_with(dir) && relative != Path::new(dir))
+            || WATCHED_FILES.iter().any(|file| relative == Path::new(file))
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use toml_edit::{Array, DocumentMut, Item, Table};
+
+/// Motif des membres du workspace couvrant toutes les solutions
+pub const SOLUTIONS_MEMBER: &str = "solutions/*/*";
+
+/// Indique si un `Cargo.toml` déclare le membre `solutions/*/*`.
+///
+/// # Errors
+///
+/// Retourne une erreur si le manifeste n'est pas un TOML valide.
+pub fn has_workspace_member(content: &str, member: &str) -> Result<bool> {
+    let doc = parse(content)?;
+    Ok(doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(Item::as_array)
+        .is_some_and(|members| members.iter().any(|m| m.as_str() == Some(member))))
+}
+
+/// Ajoute un membre au workspace d'un `Cargo.toml`, en créant la section si besoin.
+///
+/// Le reste du fichier est conservé tel quel.
+///
+/// # Errors
+///
+/// Retourne une erreur si le manifeste n'est pas un TOML valide.
+pub fn add_workspace_member(content: &str, member: &str) -> Result<String> {
+    if has_workspace_member(content, member)? {
+        return Ok(content.to_string());
+    }
+
+    let mut doc = parse(content)?;
+    let workspace = doc
+        .entry("workspace")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .context("La clé workspace du Cargo.toml n'est pas une table")?;
+    let members = workspace
+        .entry("members")
+        .or_insert(toml_edit::value(Array::new()))
+        .as_array_mut()
+        .context("La clé workspace.members du Cargo.toml n'est pas un tableau")?;
+    members.push(member);
+
+    Ok(doc.to_string())
+}
+
+/// Lignes de `required` absentes d'un fichier texte (comparaison sans espaces autour)
+pub fn missing_lines<'a>(content: &str, required: &[&'a str]) -> Vec<&'a str> {
+    required
+        .iter()
+        .copied()
+        .filter(|line| !content.lines().any(|existing| existing.trim() == *line))
+        .collect()
+}
+
+/// Ajoute à la fin d'un fichier texte les lignes manquantes.
+pub fn append_missing_lines(content: &str, required: &[&str]) -> String {
+    let missing = missing_lines(content, required);
+    if missing.is_empty() {
+        return content.to_string();
+    }
+
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    for line in missing {
+        updated.push_str(line);
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Ajoute à un fichier `.env` les clés absentes avec leur valeur par défaut.
+///
+/// Les clés déjà présentes, même vides, ne sont pas modifiées.
+pub fn add_env_keys(content: &str, keys: &[(&str, &str)]) -> String {
+    let lines: Vec<String> = keys
+        .iter()
+        .filter(|(key, _)| {
+            !content.lines().any(|line| {
+                line.trim_start()
+                    .trim_start_matches("export ")
+                    .split_once('=')
+                    .is_some_and(|(existing, _)| existing.trim() == *key)
+            })
+        })
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+    append_missing_lines(content, &refs)
+}
+
+/// Renseigne une clé présente sans valeur dans un fichier `.env` (`KEY=` ou `KEY=""`).
+///
+/// Les valeurs déjà renseignées et les autres lignes ne sont pas modifiées.
+pub fn fill_empty_env_key(content: &str, key: &str, value: &str) -> String {
+    let mut updated: String = content
+        .lines()
+        .map(|line| {
+            let (prefix, rest) = match line.trim_start().strip_prefix("export ") {
+                Some(rest) => ("export ", rest),
+                None => ("", line.trim_start()),
+            };
+            match rest.split_once('=') {
+                Some((existing, current))
+                    if existing.trim() == key && matches!(current.trim(), "" | "\"\"" | "''") =>
+                {
+                    format!("{}{}={}", prefix, key, value)
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+fn parse(content: &str) -> Result<DocumentMut> {
+    content
+        .parse::<DocumentMut>()
+        .context("Le Cargo.toml n'est pas un fichier TOML valide")
+}